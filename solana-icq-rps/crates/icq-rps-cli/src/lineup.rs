@@ -0,0 +1,130 @@
+//! Lineups read from a file or dealt at random, as `(cell, piece)` pairs.
+//!
+//! A lineup file draws the caller's spawn rows as they appear in `show`:
+//! one line per row, front row first, one letter per cell (`.` for empty).
+//! Spaces are ignored, so a player1 file for the classic board looks like
+//!
+//! ```text
+//! R P S T S P R
+//! S R P F P S R
+//! ```
+
+use anyhow::{bail, Result};
+use icq_rps_client::engine::{ArmyMode, Rules, Side, ARMY_KINDS, ARMY_PIECES};
+use icq_rps_client::program::Piece;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::view::{cell_at_view, piece_of};
+
+pub fn parse(text: &str, rules: &Rules, side: Side) -> Result<Vec<(u8, Piece)>> {
+    let (w, h, depth) = (rules.width, rules.height, rules.spawn_depth);
+    let rows: Vec<Vec<char>> = text
+        .lines()
+        .map(|l| l.chars().filter(|c| !c.is_whitespace()).collect::<Vec<_>>())
+        .filter(|r| !r.is_empty())
+        .collect();
+    if rows.len() != depth as usize {
+        bail!("expected {depth} rows, found {}", rows.len());
+    }
+
+    let mut lineup = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        if row.len() != w as usize {
+            bail!("row {}: expected {w} cells, found {}", i + 1, row.len());
+        }
+        for (col, &c) in row.iter().enumerate() {
+            if c == '.' {
+                continue;
+            }
+            let Some(piece) = piece_of(c) else {
+                bail!("row {}: unknown piece '{c}'", i + 1);
+            };
+            let idx = cell_at_view(side, w, h, col as u8, h - depth + i as u8);
+            lineup.push((idx, piece));
+        }
+    }
+    Ok(lineup)
+}
+
+/// Counts per `ARMY_PIECES` kind, as `submit_lineup` declares them.
+pub fn army_of(lineup: &[(u8, Piece)]) -> [u8; ARMY_KINDS] {
+    let mut army = [0; ARMY_KINDS];
+    for &(_, piece) in lineup {
+        if let Some(k) = ARMY_PIECES.iter().position(|&p| p as u8 == piece as u8) {
+            army[k] += 1;
+        }
+    }
+    army
+}
+
+/// A random army allowed by `rules` (or the `drafted` one) spread over the
+/// free spawn cells of `side`, traps on the rows they may stand on.
+pub fn random(
+    rules: &Rules,
+    side: Side,
+    drafted: Option<[u8; ARMY_KINDS]>,
+    rng: &mut impl Rng,
+) -> Result<Vec<(u8, Piece)>> {
+    let mut cells: Vec<u8> = (0..rules.cells() as u8)
+        .filter(|&i| rules.is_spawn(side, i) && !rules.is_blocked(i as usize))
+        .collect();
+    cells.shuffle(rng);
+    // trap-row cells first, so traps get them
+    cells.sort_by_key(|&i| !rules.is_trap_row(side, i));
+    let trap_cells = cells
+        .iter()
+        .filter(|&&i| rules.is_trap_row(side, i))
+        .count();
+    let trap = ARMY_PIECES
+        .iter()
+        .position(|&p| p as u8 == Piece::Trap as u8);
+
+    let army = match drafted {
+        Some(army) => army,
+        None => {
+            let a = &rules.army;
+            let mut army = a.min;
+            let mut total: usize = army.iter().map(|&c| c as usize).sum();
+            while total < a.size as usize {
+                let open: Vec<usize> = (0..ARMY_KINDS)
+                    .filter(|&k| army[k] < a.max[k])
+                    .filter(|&k| Some(k) != trap || (army[k] as usize) < trap_cells)
+                    .filter(|&k| {
+                        a.mode != ArmyMode::Budget
+                            || a.cost_of(&army) + a.cost[k] as u32 <= a.budget as u32
+                    })
+                    .collect();
+                let Some(&k) = open.choose(rng) else {
+                    break;
+                };
+                army[k] += 1;
+                total += 1;
+            }
+            army
+        }
+    };
+    rules
+        .army
+        .check(&army)
+        .map_err(|e| anyhow::anyhow!("no random army fits: {e:?}"))?;
+
+    let total: usize = army.iter().map(|&c| c as usize).sum();
+    if total > cells.len() {
+        bail!("{total} pieces do not fit on {} spawn cells", cells.len());
+    }
+    // traps take the front of `cells`, everything else is spread over the rest
+    let traps = trap.map_or(0, |k| army[k] as usize);
+    cells[traps..].shuffle(rng);
+    let pieces = army.iter().enumerate().flat_map(|(k, &count)| {
+        let piece = Piece::from(ARMY_PIECES[k] as u8);
+        (0..count).map(move |_| (Some(k) != trap, piece))
+    });
+    let mut pieces: Vec<(bool, Piece)> = pieces.collect();
+    pieces.sort_by_key(|&(not_trap, _)| not_trap);
+    Ok(cells
+        .into_iter()
+        .zip(pieces)
+        .map(|(cell, (_, piece))| (cell, piece))
+        .collect())
+}
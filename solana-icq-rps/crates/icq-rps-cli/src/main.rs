@@ -0,0 +1,374 @@
+//! Plays ICQ rock-paper-scissors games from a terminal.
+//!
+//! Lineup salts and weapon choices are kept in `--secrets` so pieces can be
+//! revealed when they fight. `move` reveals the attacker right away and
+//! `reveal` answers a battle as the defender.
+
+mod lineup;
+mod rpc;
+mod secrets;
+mod view;
+
+use std::fs;
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand};
+use icq_rps_client::engine::Side;
+use icq_rps_client::program::{
+    weapon_commitment, BoardCellOwner, Choice, GameConfig, Phase, Piece,
+};
+use icq_rps_client::{commit_lineup, decode_game, game_pda, ix, DecodedGame};
+use rand::Rng;
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_signer::Signer;
+
+use crate::rpc::Rpc;
+use crate::secrets::{Secrets, WeaponSecret};
+
+#[derive(Parser)]
+#[command(name = "icq-rps", version, about)]
+struct Cli {
+    /// JSON-RPC endpoint.
+    #[arg(long, short, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair that signs and pays; defaults to the Solana CLI one.
+    #[arg(long, short)]
+    keypair: Option<PathBuf>,
+    /// Directory for lineup salts and weapon choices.
+    #[arg(long, default_value = ".icq-rps")]
+    secrets: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates a game with the classic rules and prints its address.
+    Create {
+        /// Lamports each player stakes.
+        #[arg(long, default_value_t = 0)]
+        wager: u64,
+    },
+    /// Joins a game as player1.
+    Join { game: Pubkey },
+    /// Prints a game and its board from your side.
+    Show { game: Pubkey },
+    /// Places your lineup, read from `--file` or dealt at random.
+    Lineup {
+        game: Pubkey,
+        #[arg(long, conflicts_with = "random", required_unless_present = "random")]
+        file: Option<PathBuf>,
+        #[arg(long)]
+        random: bool,
+    },
+    /// Moves a piece; cells are `x,y` or board indices.
+    Move { game: Pubkey, from: Cell, to: Cell },
+    /// Reveals your pieces in the pending battle.
+    Reveal { game: Pubkey },
+    /// Picks a tie-break weapon, or reveals the one picked before.
+    Weapon {
+        game: Pubkey,
+        /// rock, paper, scissors, lizard or spock.
+        choice: Option<String>,
+    },
+}
+
+/// A cell as typed on the command line.
+#[derive(Clone, Copy)]
+enum Cell {
+    Xy(u8, u8),
+    Idx(u8),
+}
+
+impl std::str::FromStr for Cell {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        let num = |v: &str| v.trim().parse::<u8>().map_err(|e| format!("{s}: {e}"));
+        match s.split_once(',') {
+            Some((x, y)) => Ok(Cell::Xy(num(x)?, num(y)?)),
+            None => Ok(Cell::Idx(num(s)?)),
+        }
+    }
+}
+
+impl Cell {
+    fn idx(self, width: u8) -> u8 {
+        match self {
+            Cell::Xy(x, y) => y.saturating_mul(width).saturating_add(x),
+            Cell::Idx(i) => i,
+        }
+    }
+}
+
+fn parse_choice(s: &str) -> Result<Choice> {
+    Ok(match s.to_ascii_lowercase().as_str() {
+        "rock" | "r" => Choice::Rock,
+        "paper" | "p" => Choice::Paper,
+        "scissors" | "s" => Choice::Scissors,
+        "lizard" | "l" => Choice::Lizard,
+        "spock" | "v" => Choice::Spock,
+        _ => bail!("unknown weapon '{s}'"),
+    })
+}
+
+struct Client {
+    rpc: Rpc,
+    payer: Keypair,
+    secrets: PathBuf,
+}
+
+impl Client {
+    fn me(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    fn fetch(&self, game: &Pubkey) -> Result<DecodedGame> {
+        let data = self
+            .rpc
+            .account_data(game)?
+            .ok_or_else(|| anyhow!("no game at {game}"))?;
+        decode_game(&data).map_err(|e| anyhow!("{game} is not a game: {e}"))
+    }
+
+    fn send(&self, ix: anchor_lang::solana_program::instruction::Instruction) -> Result<String> {
+        self.rpc.send(&[ix], &self.payer)
+    }
+
+    fn secrets_path(&self, game: &Pubkey) -> PathBuf {
+        Secrets::path(&self.secrets, game, &self.me())
+    }
+
+    fn load_secrets(&self, game: &Pubkey) -> Result<Secrets> {
+        Secrets::load(&self.secrets_path(game))
+    }
+
+    fn side(&self, g: &DecodedGame) -> Result<Side> {
+        g.game
+            .side_of(&self.me())
+            .ok_or_else(|| anyhow!("{} does not play this game", self.me()))
+    }
+}
+
+fn main() {
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("error: {e:#}");
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let keypair = match cli.keypair {
+        Some(path) => path,
+        None => {
+            let home = std::env::var("HOME").context("HOME is not set")?;
+            PathBuf::from(home).join(".config/solana/id.json")
+        }
+    };
+    let payer = read_keypair_file(&keypair)
+        .map_err(|e| anyhow!("reading keypair {}: {e}", keypair.display()))?;
+    let c = Client {
+        rpc: Rpc::new(cli.url),
+        payer,
+        secrets: cli.secrets,
+    };
+
+    match cli.command {
+        Command::Create { wager } => create(&c, wager),
+        Command::Join { game } => {
+            c.send(ix::join_game(&game, &c.me(), None))?;
+            show(&c, &game)
+        }
+        Command::Show { game } => show(&c, &game),
+        Command::Lineup { game, file, random } => place_lineup(&c, &game, file, random),
+        Command::Move { game, from, to } => move_piece(&c, &game, from, to),
+        Command::Reveal { game } => {
+            if !reveal_battle(&c, &game)? {
+                bail!("none of your pieces is waiting to be revealed");
+            }
+            show(&c, &game)
+        }
+        Command::Weapon { game, choice } => weapon(&c, &game, choice.as_deref()),
+    }
+}
+
+fn create(c: &Client, wager: u64) -> Result<()> {
+    let nonce: [u8; 32] = rand::thread_rng().gen();
+    let game = game_pda(&c.me(), &nonce).0;
+    let config = GameConfig::default();
+    c.send(ix::create_game(&c.me(), nonce, wager, config, None))?;
+    println!("created {game}");
+    Ok(())
+}
+
+fn show(c: &Client, game: &Pubkey) -> Result<()> {
+    let g = c.fetch(game)?;
+    let secrets = c.load_secrets(game)?;
+    let me = g.game.side_of(&c.me());
+    let you = |side: Side| if me == Some(side) { " (you)" } else { "" };
+
+    println!("game     {game}");
+    println!("phase    {:?}", g.phase);
+    println!("player0  {}{}", g.game.player0, you(Side::P0));
+    println!("player1  {}{}", g.game.player1, you(Side::P1));
+    if g.game.wager > 0 {
+        println!("wager    {}", g.game.wager);
+    }
+    let board = g.board();
+    if g.phase == Phase::Active {
+        println!("turn     player{}{}", board.turn as u8, you(board.turn));
+        if let Some((from, to)) = board.battle {
+            let w = g.game.config.width;
+            let xy = |i: u8| format!("{},{}", i % w, i / w);
+            println!("battle   {} -> {}: waiting for reveals", xy(from), xy(to));
+        } else if let Some(tie) = board.tie {
+            println!(
+                "tie      round {}: both players pick a weapon",
+                tie.round + 1
+            );
+        } else if me == Some(board.turn) {
+            let known = secrets.known_pieces();
+            println!(
+                "moves    {} legal",
+                board.legal_moves_knowing(&known).count()
+            );
+        }
+    }
+    if g.phase == Phase::Finished {
+        match g.game.winner {
+            Some(w) => println!("winner   {w}"),
+            None => println!("winner   none, a draw"),
+        }
+    }
+    println!();
+    print!("{}", view::render(&g, me, &secrets));
+    Ok(())
+}
+
+fn place_lineup(c: &Client, game: &Pubkey, file: Option<PathBuf>, random: bool) -> Result<()> {
+    let g = c.fetch(game)?;
+    let side = c.side(&g)?;
+    let rules = g.game.config.rules();
+    let pieces = match (file, random) {
+        (Some(path), false) => {
+            let text =
+                fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            lineup::parse(&text, &rules, side)?
+        }
+        (None, true) => {
+            let board = g.board();
+            let drafted = rules.army.draft.then(|| board.army[side.idx()]);
+            lineup::random(&rules, side, drafted, &mut rand::thread_rng())?
+        }
+        _ => bail!("pass exactly one of --file and --random"),
+    };
+
+    let mut rng = rand::thread_rng();
+    let salted: Vec<(u8, Piece, [u8; 32])> = pieces
+        .iter()
+        .map(|&(cell, piece)| (cell, piece, rng.gen()))
+        .collect();
+    let (root, leaves) = commit_lineup(&salted);
+    let positions = pieces.iter().map(|&(cell, _)| cell).collect();
+    let army = lineup::army_of(&pieces);
+
+    // saved first: a lineup that lands without its salts can never be revealed
+    let mut secrets = c.load_secrets(game)?;
+    secrets.set_lineup(leaves);
+    secrets.save(&c.secrets_path(game))?;
+    c.send(ix::submit_lineup(game, &c.me(), positions, army, root))?;
+    show(c, game)
+}
+
+fn move_piece(c: &Client, game: &Pubkey, from: Cell, to: Cell) -> Result<()> {
+    let ix = match (from, to) {
+        (Cell::Xy(fx, fy), Cell::Xy(tx, ty)) => {
+            ix::move_piece_xy(game, &c.me(), (fx, fy), (tx, ty))
+        }
+        _ => {
+            let width = c.fetch(game)?.game.config.width;
+            ix::move_piece(game, &c.me(), from.idx(width), to.idx(width))
+        }
+    };
+    c.send(ix)?;
+    reveal_battle(c, game)?;
+    show(c, game)
+}
+
+/// Reveals whichever of your pieces the pending battle still waits for.
+/// Returns false when there was nothing to reveal.
+fn reveal_battle(c: &Client, game: &Pubkey) -> Result<bool> {
+    let g = c.fetch(game)?;
+    let side = c.side(&g)?;
+    let Some((from, to)) = g.board().battle else {
+        return Ok(false);
+    };
+    let mine = match side {
+        Side::P0 => BoardCellOwner::P0,
+        Side::P1 => BoardCellOwner::P1,
+    };
+    let secrets = c.load_secrets(game)?;
+    let mut revealed = false;
+    for idx in [from, to] {
+        let cell = idx as usize;
+        if BoardCellOwner::from(g.game.board_cells_owner[cell]) != mine
+            || Piece::from(g.game.board_pieces[cell]) != Piece::Unknown
+        {
+            continue;
+        }
+        let origin = g.game.board_origin[cell];
+        let leaf = secrets
+            .leaf(origin)
+            .ok_or_else(|| anyhow!("no saved lineup secret for the piece from cell {origin}"))?;
+        let piece = Piece::from(leaf.piece);
+        c.send(ix::reveal_piece(
+            game,
+            &c.me(),
+            idx,
+            piece,
+            leaf.salt,
+            leaf.proof.clone(),
+        ))?;
+        println!("revealed {:?} on {idx}", piece);
+        revealed = true;
+    }
+    Ok(revealed)
+}
+
+fn weapon(c: &Client, game: &Pubkey, choice: Option<&str>) -> Result<()> {
+    let path = c.secrets_path(game);
+    let mut secrets = c.load_secrets(game)?;
+    let g = c.fetch(game)?;
+    let side = c.side(&g)?;
+    let committed = |g: &DecodedGame| [g.game.commit_made0, g.game.commit_made1];
+
+    if let Some(choice) = choice {
+        if committed(&g)[side.idx()] {
+            bail!("you already picked a weapon this round, run `weapon` to reveal it");
+        }
+        let secret = WeaponSecret {
+            choice: parse_choice(choice)? as u8,
+            salt: rand::thread_rng().gen(),
+        };
+        secrets.weapon = Some(secret);
+        secrets.save(&path)?;
+        let commitment = weapon_commitment(secret.choice, &secret.salt);
+        c.send(ix::commit_weapon(game, &c.me(), commitment))?;
+        println!("committed {choice}");
+    }
+
+    let g = c.fetch(game)?;
+    if !committed(&g).iter().all(|&made| made) {
+        println!("waiting for the opponent to pick, then run `weapon` to reveal");
+        return Ok(());
+    }
+    let Some(secret) = secrets.weapon else {
+        bail!("no saved weapon for this game");
+    };
+    let choice = Choice::from(secret.choice);
+    c.send(ix::reveal_weapon(game, &c.me(), choice, secret.salt))?;
+    println!("revealed {choice:?}");
+    show(c, game)
+}
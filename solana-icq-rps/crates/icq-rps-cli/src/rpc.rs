@@ -0,0 +1,136 @@
+//! Just enough JSON-RPC to read accounts and land transactions.
+
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use icq_rps_client::error_code;
+use serde_json::{json, Value};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+/// How long `send` waits for a transaction to be confirmed.
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct Rpc {
+    url: String,
+}
+
+impl Rpc {
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut reply: Value = ureq::post(&self.url)
+            .send_json(body)
+            .with_context(|| format!("{method} to {}", self.url))?
+            .into_json()?;
+        if let Some(err) = reply.get("error") {
+            bail!("{}", describe_rpc_error(err));
+        }
+        Ok(reply["result"].take())
+    }
+
+    /// Raw data of `key`, `None` if the account does not exist.
+    pub fn account_data(&self, key: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([key.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+        let Some(data) = result["value"]["data"][0].as_str() else {
+            return Ok(None);
+        };
+        Ok(Some(STANDARD.decode(data)?))
+    }
+
+    fn latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let hash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("getLatestBlockhash: no blockhash"))?;
+        Ok(Hash::from_str(hash)?)
+    }
+
+    /// Signs `ixs` with `payer`, sends them and waits until they are
+    /// confirmed. Failures are reported as the program's `ErrorCode`.
+    pub fn send(&self, ixs: &[Instruction], payer: &Keypair) -> Result<String> {
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&payer.pubkey()),
+            &[payer],
+            self.latest_blockhash()?,
+        );
+        let wire = STANDARD.encode(bincode::serialize(&tx)?);
+        let sig = self.call(
+            "sendTransaction",
+            json!([wire, { "encoding": "base64", "preflightCommitment": "confirmed" }]),
+        )?;
+        let sig = sig
+            .as_str()
+            .ok_or_else(|| anyhow!("sendTransaction: no signature"))?
+            .to_string();
+
+        let mut waited = Duration::ZERO;
+        while waited < CONFIRM_TIMEOUT {
+            let result = self.call("getSignatureStatuses", json!([[sig]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("{}", describe_tx_error(&status["err"]));
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed" | "finalized")
+                ) {
+                    return Ok(sig);
+                }
+            }
+            thread::sleep(POLL_INTERVAL);
+            waited += POLL_INTERVAL;
+        }
+        bail!(
+            "{sig} was not confirmed within {}s",
+            CONFIRM_TIMEOUT.as_secs()
+        )
+    }
+}
+
+/// Names a failed instruction after the program's `ErrorCode`, e.g.
+/// `NotYourTurn (6019): Not your turn`.
+fn program_error(err: &Value) -> Option<String> {
+    let code = err["InstructionError"][1]["Custom"].as_u64()? as u32;
+    let e = error_code(code)?;
+    Some(format!("{} ({code}): {e}", e.name()))
+}
+
+fn describe_tx_error(err: &Value) -> String {
+    program_error(err).unwrap_or_else(|| err.to_string())
+}
+
+/// A failed preflight carries the transaction error and the program logs;
+/// errors raised by Anchor itself are only named in the logs.
+fn describe_rpc_error(err: &Value) -> String {
+    let data = &err["data"];
+    let anchor_log = || {
+        data["logs"]
+            .as_array()?
+            .iter()
+            .filter_map(Value::as_str)
+            .filter_map(|l| l.strip_prefix("Program log: "))
+            .find(|l| l.starts_with("AnchorError"))
+            .map(str::to_string)
+    };
+    program_error(&data["err"])
+        .or_else(anchor_log)
+        .unwrap_or_else(|| err["message"].as_str().unwrap_or("RPC error").to_string())
+}
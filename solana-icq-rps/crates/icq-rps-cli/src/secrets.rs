@@ -0,0 +1,134 @@
+//! Salts and proofs a player needs later in a game. They never leave this
+//! machine: losing them means a hidden piece can no longer be revealed.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use icq_rps_client::engine::{self, MAX_CELLS};
+use icq_rps_client::program::Piece;
+use icq_rps_client::LineupLeaf;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Leaf {
+    pub cell: u8,
+    pub piece: u8,
+    pub salt: [u8; 32],
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct WeaponSecret {
+    pub choice: u8,
+    pub salt: [u8; 32],
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Secrets {
+    /// One leaf per lineup piece, keyed by its spawn cell.
+    pub lineup: Vec<Leaf>,
+    /// The weapon committed in the current tie-break round.
+    pub weapon: Option<WeaponSecret>,
+}
+
+impl Secrets {
+    /// Where `player` keeps the secrets of `game` inside `dir`.
+    pub fn path(dir: &Path, game: &Pubkey, player: &Pubkey) -> PathBuf {
+        dir.join(format!("{game}-{player}.json"))
+    }
+
+    /// Empty when nothing was saved yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_slice(&raw).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Only the owner may read the file: the salts are what keep the
+    /// lineup hidden.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let context = || format!("writing {}", path.display());
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)
+            .with_context(context)?;
+        // `mode` only applies to new files; older ones may be readable
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .with_context(context)?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)
+            .with_context(context)
+    }
+
+    pub fn set_lineup(&mut self, leaves: Vec<LineupLeaf>) {
+        self.lineup = leaves
+            .into_iter()
+            .map(|l| Leaf {
+                cell: l.cell,
+                piece: l.piece as u8,
+                salt: l.salt,
+                proof: l.proof,
+            })
+            .collect();
+    }
+
+    /// The leaf of the piece that spawned on `origin`.
+    pub fn leaf(&self, origin: u8) -> Option<&Leaf> {
+        self.lineup.iter().find(|l| l.cell == origin)
+    }
+
+    pub fn piece(&self, origin: u8) -> Option<Piece> {
+        self.leaf(origin).map(|l| Piece::from(l.piece))
+    }
+
+    /// The own lineup by spawn cell, for `Board::legal_moves_knowing`.
+    pub fn known_pieces(&self) -> [engine::Piece; MAX_CELLS] {
+        let mut known = [engine::Piece::Unknown; MAX_CELLS];
+        for l in &self.lineup {
+            known[l.cell as usize] = Piece::from(l.piece).into();
+        }
+        known
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_secrets_are_private() {
+        let dir = std::env::temp_dir().join(format!("icq-rps-secrets-{}", std::process::id()));
+        let path = dir.join("game.json");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, b"{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let secrets = Secrets {
+            weapon: Some(WeaponSecret {
+                choice: 1,
+                salt: [9; 32],
+            }),
+            ..Secrets::default()
+        };
+        secrets.save(&path).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(
+            Secrets::load(&path).unwrap().weapon.map(|w| w.salt),
+            Some([9; 32])
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
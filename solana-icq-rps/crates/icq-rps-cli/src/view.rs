@@ -0,0 +1,93 @@
+//! The board as one player sees it: their own spawn rows at the bottom.
+
+use std::fmt::Write;
+
+use icq_rps_client::engine::Side;
+use icq_rps_client::program::{BoardCellOwner, Piece};
+use icq_rps_client::DecodedGame;
+
+use crate::secrets::Secrets;
+
+/// The cell shown at `row`, `col` of a `width` x `height` board. Player1
+/// sees the board turned around.
+pub fn cell_at_view(side: Side, width: u8, height: u8, col: u8, row: u8) -> u8 {
+    match side {
+        Side::P0 => row * width + col,
+        Side::P1 => (height - 1 - row) * width + (width - 1 - col),
+    }
+}
+
+pub fn letter(piece: Piece) -> char {
+    match piece {
+        Piece::Empty => '.',
+        Piece::Rock => 'R',
+        Piece::Paper => 'P',
+        Piece::Scissors => 'S',
+        Piece::Flag => 'F',
+        Piece::Trap => 'T',
+        Piece::Unknown => '?',
+        Piece::Lizard => 'L',
+        Piece::Spock => 'V',
+        Piece::Scout => 'C',
+        Piece::Bomb => 'B',
+    }
+}
+
+/// Inverse of `letter` for the pieces a lineup is made of, either case.
+pub fn piece_of(c: char) -> Option<Piece> {
+    Some(match c.to_ascii_uppercase() {
+        'R' => Piece::Rock,
+        'P' => Piece::Paper,
+        'S' => Piece::Scissors,
+        'F' => Piece::Flag,
+        'T' => Piece::Trap,
+        'L' => Piece::Lizard,
+        'V' => Piece::Spock,
+        'C' => Piece::Scout,
+        'B' => Piece::Bomb,
+        _ => return None,
+    })
+}
+
+/// Renders the board from `me`'s side, spectators watch from player0's.
+/// Own pieces are upper case, taken from `secrets` while still hidden;
+/// opponent pieces are lower case, `?` until revealed. `#` is blocked.
+pub fn render(g: &DecodedGame, me: Option<Side>, secrets: &Secrets) -> String {
+    let (w, h) = (g.game.config.width, g.game.config.height);
+    let side = me.unwrap_or(Side::P0);
+    let mine = me.map(|s| match s {
+        Side::P0 => BoardCellOwner::P0,
+        Side::P1 => BoardCellOwner::P1,
+    });
+
+    let mut out = String::from("   ");
+    for col in 0..w {
+        let x = cell_at_view(side, w, h, col, 0) % w;
+        let _ = write!(out, "{x:>2}");
+    }
+    out.push('\n');
+    for row in 0..h {
+        let y = cell_at_view(side, w, h, 0, row) / w;
+        let _ = write!(out, "{y:>2} ");
+        for col in 0..w {
+            let idx = cell_at_view(side, w, h, col, row);
+            let (x, y) = (idx % w, idx / w);
+            let (owner, piece) = (g.owner(x, y), g.piece(x, y));
+            let c = match owner {
+                BoardCellOwner::Blocked => '#',
+                BoardCellOwner::None => '.',
+                o if Some(o) == mine => {
+                    let origin = g.game.board_origin[idx as usize];
+                    match piece {
+                        Piece::Unknown => secrets.piece(origin).map_or('?', letter),
+                        p => letter(p),
+                    }
+                }
+                _ => letter(piece).to_ascii_lowercase(),
+            };
+            let _ = write!(out, " {c}");
+        }
+        out.push('\n');
+    }
+    out
+}
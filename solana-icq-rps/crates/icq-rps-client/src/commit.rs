@@ -0,0 +1,75 @@
+use solana_icq_rps::{lineup_leaf, lineup_node, Piece, LINEUP_TREE_DEPTH};
+
+/// What a player keeps to reveal one lineup piece later.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineupLeaf {
+    pub cell: u8,
+    pub piece: Piece,
+    pub salt: [u8; 32],
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Builds the lineup tree `verify_lineup_proof` checks: every piece goes in
+/// the slot of its spawn cell as a `(cell, piece, salt)` leaf, unused slots
+/// stay zero. Returns the root to submit and the proof for every piece.
+pub fn commit_lineup(pieces: &[(u8, Piece, [u8; 32])]) -> ([u8; 32], Vec<LineupLeaf>) {
+    let mut level = vec![[0u8; 32]; 1 << LINEUP_TREE_DEPTH];
+    for &(cell, piece, salt) in pieces {
+        level[cell as usize] = lineup_leaf(cell, piece as u8, &salt);
+    }
+    let mut proofs = vec![Vec::with_capacity(LINEUP_TREE_DEPTH); pieces.len()];
+
+    for depth in 0..LINEUP_TREE_DEPTH {
+        for (&(cell, ..), proof) in pieces.iter().zip(&mut proofs) {
+            proof.push(level[((cell as usize) >> depth) ^ 1]);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| lineup_node(&pair[0], &pair[1]))
+            .collect();
+    }
+
+    let leaves = pieces
+        .iter()
+        .zip(proofs)
+        .map(|(&(cell, piece, salt), proof)| LineupLeaf {
+            cell,
+            piece,
+            salt,
+            proof,
+        })
+        .collect();
+    (level[0], leaves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_icq_rps::verify_lineup_proof;
+
+    #[test]
+    fn every_lineup_proof_verifies_on_chain() {
+        // a full 10x10 spawn zone edge to edge, plus a lone piece
+        let pieces: Vec<_> = (0..20)
+            .chain([99])
+            .map(|cell| (cell, Piece::Rock, [cell; 32]))
+            .collect();
+        let (root, leaves) = commit_lineup(&pieces);
+        assert_eq!(leaves.len(), pieces.len());
+
+        for leaf in &leaves {
+            let hash = lineup_leaf(leaf.cell, leaf.piece as u8, &leaf.salt);
+            assert!(verify_lineup_proof(&root, leaf.cell, hash, &leaf.proof));
+            // the proof holds for this piece on this cell only
+            let lie = lineup_leaf(leaf.cell, Piece::Flag as u8, &leaf.salt);
+            assert!(!verify_lineup_proof(&root, leaf.cell, lie, &leaf.proof));
+            let moved = lineup_leaf(leaf.cell ^ 1, leaf.piece as u8, &leaf.salt);
+            assert!(!verify_lineup_proof(
+                &root,
+                leaf.cell ^ 1,
+                moved,
+                &leaf.proof
+            ));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::AccountDeserialize;
+use icq_rps_engine::Board;
+use solana_icq_rps::{BoardCellOwner, Game, Phase, Piece};
+
+/// A `Game` account with its board laid out as `[y][x]` grids. Player1's
+/// spawn rows come first.
+#[derive(Clone)]
+pub struct DecodedGame {
+    pub game: Game,
+    pub phase: Phase,
+    pub pieces: Vec<Vec<Piece>>,
+    pub owners: Vec<Vec<BoardCellOwner>>,
+}
+
+impl DecodedGame {
+    pub fn piece(&self, x: u8, y: u8) -> Piece {
+        self.pieces[y as usize][x as usize]
+    }
+
+    pub fn owner(&self, x: u8, y: u8) -> BoardCellOwner {
+        self.owners[y as usize][x as usize]
+    }
+
+    /// The game as the rules engine sees it, for legal moves and simulation.
+    pub fn board(&self) -> Board {
+        self.game.board()
+    }
+}
+
+/// Decodes raw `Game` account data, discriminator included.
+pub fn decode_game(data: &[u8]) -> anchor_lang::Result<DecodedGame> {
+    let game = Game::try_deserialize(&mut &data[..])?;
+    let width = game.config.width as usize;
+    let cells = game.config.cells();
+    let pieces = game.board_pieces[..cells]
+        .chunks(width)
+        .map(|row| row.iter().map(|&p| Piece::from(p)).collect())
+        .collect();
+    let owners = game.board_cells_owner[..cells]
+        .chunks(width)
+        .map(|row| row.iter().map(|&o| BoardCellOwner::from(o)).collect())
+        .collect();
+    Ok(DecodedGame {
+        phase: game.phase(),
+        game,
+        pieces,
+        owners,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Discriminator};
+    use icq_rps_engine as engine;
+    use solana_icq_rps::GameConfig;
+
+    /// A zeroed `Game` account read back the way the program stores it.
+    fn blank_game() -> Game {
+        let mut data = Game::DISCRIMINATOR.to_vec();
+        data.resize(Game::SIZE, 0);
+        Game::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn decode_game_lays_the_board_out_by_row() {
+        let mut game = blank_game();
+        game.config = GameConfig {
+            width: 5,
+            height: 4,
+            spawn_depth: 1,
+            trap_rows: 1,
+            ..GameConfig::default()
+        };
+        game.phase = Phase::Active as u8;
+        let mut board = game.board();
+        let (rock, hidden) = (board.rules.cell_at(4, 0).unwrap(), 6);
+        board.owner[rock as usize] = engine::Owner::P1;
+        board.piece[rock as usize] = engine::Piece::Rock;
+        board.owner[hidden] = engine::Owner::P0;
+        board.piece[hidden] = engine::Piece::Unknown;
+        game.store(&board);
+
+        let mut data = Vec::new();
+        game.try_serialize(&mut data).unwrap();
+        let decoded = decode_game(&data).unwrap();
+        assert_eq!(decoded.phase, Phase::Active);
+        assert_eq!(decoded.pieces.len(), 4);
+        assert!(decoded.pieces.iter().all(|row| row.len() == 5));
+        assert_eq!(decoded.piece(4, 0), Piece::Rock);
+        assert_eq!(decoded.owner(4, 0), BoardCellOwner::P1);
+        assert_eq!(decoded.piece(1, 1), Piece::Unknown);
+        assert_eq!(decoded.owner(1, 1), BoardCellOwner::P0);
+        assert_eq!(decoded.owner(0, 3), BoardCellOwner::None);
+        assert_eq!(decoded.board(), game.board());
+    }
+
+    #[test]
+    fn decode_game_refuses_other_accounts() {
+        let mut data = Vec::new();
+        blank_game().try_serialize(&mut data).unwrap();
+        assert!(decode_game(&data[..data.len() - 1]).is_err());
+        data[0] ^= 1;
+        assert!(decode_game(&data).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use solana_icq_rps::errors::ErrorCode;
+
+/// Every program error, in declaration order.
+pub const ERROR_CODES: [ErrorCode; 81] = [
+    ErrorCode::InvalidGameId,
+    ErrorCode::NotAllowedJoinGame,
+    ErrorCode::PlaceFlagDeadlinePassed,
+    ErrorCode::BadCell,
+    ErrorCode::BadPhase,
+    ErrorCode::NoGame,
+    ErrorCode::CellTaken,
+    ErrorCode::NotParticipant,
+    ErrorCode::Player0BadRow,
+    ErrorCode::Player1BadRow,
+    ErrorCode::Player0FlagAlreadyPlaced,
+    ErrorCode::Player1FlagAlreadyPlaced,
+    ErrorCode::Player0LineupAlreadyPlaced,
+    ErrorCode::Player1LineupAlreadyPlaced,
+    ErrorCode::LineupLengthMismatch,
+    ErrorCode::LineupPositionsEmpty,
+    ErrorCode::GameNotActive,
+    ErrorCode::TieInProgress,
+    ErrorCode::InvalidMove,
+    ErrorCode::NotYourTurn,
+    ErrorCode::CannotStackOwnPiece,
+    ErrorCode::NoTiePending,
+    ErrorCode::AlreadyChose,
+    ErrorCode::Overflow,
+    ErrorCode::BattleInProgress,
+    ErrorCode::NoBattlePending,
+    ErrorCode::NotInBattle,
+    ErrorCode::PieceAlreadyRevealed,
+    ErrorCode::InvalidRevealProof,
+    ErrorCode::DuplicateCommitment,
+    ErrorCode::WaitingForCommitments,
+    ErrorCode::InvalidChoice,
+    ErrorCode::InvalidWeaponReveal,
+    ErrorCode::NothingToClaim,
+    ErrorCode::RevealWindowOpen,
+    ErrorCode::InvalidTimeout,
+    ErrorCode::TimeoutNotReached,
+    ErrorCode::WagerTooSmall,
+    ErrorCode::PotUnclaimed,
+    ErrorCode::MissingTokenAccounts,
+    ErrorCode::WrongMint,
+    ErrorCode::WrongVault,
+    ErrorCode::FeeTooHigh,
+    ErrorCode::NotAdmin,
+    ErrorCode::WrongTreasury,
+    ErrorCode::DrawAlreadyOffered,
+    ErrorCode::NoDrawOffer,
+    ErrorCode::InvalidBoardSize,
+    ErrorCode::InvalidSpawnDepth,
+    ErrorCode::FlagCannotMove,
+    ErrorCode::InvalidArmyRules,
+    ErrorCode::ArmySizeMismatch,
+    ErrorCode::TooFewPieces,
+    ErrorCode::TooManyPieces,
+    ErrorCode::OverBudget,
+    ErrorCode::PieceNotInPool,
+    ErrorCode::DraftPickBlocked,
+    ErrorCode::ArmyNotDrafted,
+    ErrorCode::InvalidTerrain,
+    ErrorCode::CellBlocked,
+    ErrorCode::InvalidTieRounds,
+    ErrorCode::WeaponExhausted,
+    ErrorCode::InvalidWeaponStock,
+    ErrorCode::TooFewRock,
+    ErrorCode::TooFewPaper,
+    ErrorCode::TooFewScissors,
+    ErrorCode::TooFewFlag,
+    ErrorCode::TooFewTrap,
+    ErrorCode::TooFewLizard,
+    ErrorCode::TooFewSpock,
+    ErrorCode::TooFewScout,
+    ErrorCode::TooFewBomb,
+    ErrorCode::TooManyRock,
+    ErrorCode::TooManyPaper,
+    ErrorCode::TooManyScissors,
+    ErrorCode::TooManyFlag,
+    ErrorCode::TooManyTrap,
+    ErrorCode::TooManyLizard,
+    ErrorCode::TooManySpock,
+    ErrorCode::TooManyScout,
+    ErrorCode::TooManyBomb,
+];
+
+/// The program error behind a custom error number, as it shows up in
+/// transaction errors and `simulate_move` previews.
+pub fn error_code(code: u32) -> Option<ErrorCode> {
+    ERROR_CODES.iter().copied().find(|&e| u32::from(e) == code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Variant names in the order the program's `errors.rs` declares them.
+    fn declared() -> Vec<&'static str> {
+        let src = include_str!("../../../programs/solana-icq-rps/src/errors.rs");
+        let body = src.split("pub enum ErrorCode {").nth(1).unwrap();
+        body[..body.find("\n}").unwrap()]
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("#[") && !l.starts_with("//"))
+            .map(|l| l.trim_end_matches(','))
+            .collect()
+    }
+
+    #[test]
+    fn error_codes_follow_the_program_declaration() {
+        let names: Vec<String> = ERROR_CODES.iter().map(|e| e.name()).collect();
+        assert_eq!(names, declared());
+    }
+
+    #[test]
+    fn every_error_number_maps_back_to_its_code() {
+        for (i, &e) in ERROR_CODES.iter().enumerate() {
+            let code = 6000 + i as u32;
+            assert_eq!(u32::from(e), code);
+            assert_eq!(error_code(code).map(u32::from), Some(code));
+        }
+        assert!(error_code(6000 + ERROR_CODES.len() as u32).is_none());
+        assert!(error_code(0).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! Instruction builders. Account lists come from the program's own
+//! `#[derive(Accounts)]` structs and data from its `#[program]` arguments.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use solana_icq_rps::{accounts, instruction, Choice, GameConfig, Piece, ARMY_KINDS};
+
+use crate::{config_pda, escrow_pda, game_pda, vault_pda, ID};
+
+/// Token accounts for an SPL wager; SOL games leave them out.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TokenWager {
+    pub mint: Pubkey,
+    /// The signer's token account for `mint`.
+    pub token_account: Pubkey,
+    pub token_program: Pubkey,
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Creates the game at `game_pda(payer, nonce)` and stakes `wager`.
+pub fn create_game(
+    payer: &Pubkey,
+    nonce: [u8; 32],
+    wager: u64,
+    config: GameConfig,
+    token: Option<&TokenWager>,
+) -> Instruction {
+    let game = game_pda(payer, &nonce).0;
+    build(
+        accounts::CreateGame {
+            game,
+            config: config_pda().0,
+            escrow: escrow_pda(&game).0,
+            mint: token.map(|t| t.mint),
+            vault: token.map(|_| vault_pda(&game).0),
+            payer_token: token.map(|t| t.token_account),
+            token_program: token.map(|t| t.token_program),
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateGame {
+            nonce,
+            wager,
+            config,
+        },
+    )
+}
+
+/// Joins `game` as player1, matching the creator's wager.
+pub fn join_game(game: &Pubkey, joiner: &Pubkey, token: Option<&TokenWager>) -> Instruction {
+    build(
+        accounts::JoinGame {
+            game: *game,
+            escrow: escrow_pda(game).0,
+            joiner: *joiner,
+            system_program: system_program::ID,
+            mint: token.map(|t| t.mint),
+            vault: token.map(|_| vault_pda(game).0),
+            joiner_token: token.map(|t| t.token_account),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::JoinGame {},
+    )
+}
+
+pub fn submit_lineup(
+    game: &Pubkey,
+    signer: &Pubkey,
+    positions: Vec<u8>,
+    army: [u8; ARMY_KINDS],
+    commitment: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::SubmitLineup {
+            game: *game,
+            signer: *signer,
+        },
+        instruction::SubmitLineup {
+            positions,
+            army,
+            commitment,
+        },
+    )
+}
+
+pub fn submit_lineup_xy(
+    game: &Pubkey,
+    signer: &Pubkey,
+    xs: Vec<u8>,
+    ys: Vec<u8>,
+    army: [u8; ARMY_KINDS],
+    commitment: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::SubmitLineupXy {
+            inner: accounts::SubmitLineup {
+                game: *game,
+                signer: *signer,
+            },
+        },
+        instruction::SubmitLineupXy {
+            xs,
+            ys,
+            army,
+            commitment,
+        },
+    )
+}
+
+pub fn move_piece(game: &Pubkey, signer: &Pubkey, from_idx: u8, to_idx: u8) -> Instruction {
+    build(
+        accounts::MovePiece {
+            game: *game,
+            signer: *signer,
+        },
+        instruction::MovePiece { from_idx, to_idx },
+    )
+}
+
+pub fn move_piece_xy(
+    game: &Pubkey,
+    signer: &Pubkey,
+    (from_x, from_y): (u8, u8),
+    (to_x, to_y): (u8, u8),
+) -> Instruction {
+    build(
+        accounts::MovePiece {
+            game: *game,
+            signer: *signer,
+        },
+        instruction::MovePieceXy {
+            from_x,
+            from_y,
+            to_x,
+            to_y,
+        },
+    )
+}
+
+/// Read-only; send it through `simulateTransaction` and decode the
+/// `MovePreview` return data.
+pub fn simulate_move(game: &Pubkey, signer: &Pubkey, from_idx: u8, to_idx: u8) -> Instruction {
+    build(
+        accounts::SimulateMove {
+            game: *game,
+            signer: *signer,
+        },
+        instruction::SimulateMove { from_idx, to_idx },
+    )
+}
+
+pub fn reveal_piece(
+    game: &Pubkey,
+    signer: &Pubkey,
+    idx: u8,
+    piece: Piece,
+    salt: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::RevealPiece {
+            game: *game,
+            signer: *signer,
+        },
+        instruction::RevealPiece {
+            idx,
+            piece: piece as u8,
+            salt,
+            proof,
+        },
+    )
+}
+
+fn choose_weapon(game: &Pubkey, signer: &Pubkey) -> accounts::ChooseWeapon {
+    accounts::ChooseWeapon {
+        game: *game,
+        signer: *signer,
+    }
+}
+
+/// Commits to a tie-break weapon, see `weapon_commitment`.
+pub fn commit_weapon(game: &Pubkey, signer: &Pubkey, commitment: [u8; 32]) -> Instruction {
+    build(
+        choose_weapon(game, signer),
+        instruction::CommitWeapon { commitment },
+    )
+}
+
+pub fn reveal_weapon(
+    game: &Pubkey,
+    signer: &Pubkey,
+    choice: Choice,
+    salt: [u8; 32],
+) -> Instruction {
+    build(
+        choose_weapon(game, signer),
+        instruction::RevealWeapon {
+            choice: choice as u8,
+            salt,
+        },
+    )
+}
+
+pub fn claim_tie_forfeit(game: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(choose_weapon(game, signer), instruction::ClaimTieForfeit {})
+}
@@ -0,0 +1,21 @@
+//! Rust client for the ICQ rock-paper-scissors program: account addresses,
+//! instruction builders, lineup commitments, `Game` decoding and error
+//! lookup. Everything is built from the program's own types, so it cannot
+//! drift from it.
+
+pub mod commit;
+pub use commit::*;
+
+pub mod decode;
+pub use decode::*;
+
+pub mod error;
+pub use error::*;
+
+pub mod ix;
+
+pub mod pda;
+pub use pda::*;
+
+pub use icq_rps_engine as engine;
+pub use solana_icq_rps::{self as program, ID};
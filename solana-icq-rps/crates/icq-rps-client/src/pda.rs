@@ -0,0 +1,67 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::ID;
+
+/// A game account, one per creator and nonce.
+pub fn game_pda(payer: &Pubkey, nonce: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"game", payer.as_ref(), nonce], &ID)
+}
+
+/// Holds both SOL wagers of `game`.
+pub fn escrow_pda(game: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"escrow", game.as_ref()], &ID)
+}
+
+/// Token account holding both SPL wagers of `game`.
+pub fn vault_pda(game: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", game.as_ref()], &ID)
+}
+
+/// The protocol config with the fee and treasury.
+pub fn config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ix;
+    use solana_icq_rps::GameConfig;
+
+    /// The seeds of the program's `seeds = [...]` constraints, with the
+    /// bump the client found.
+    fn derives(seeds: &[&[u8]], (key, bump): (Pubkey, u8)) -> bool {
+        let mut seeds = seeds.to_vec();
+        let bump = [bump];
+        seeds.push(&bump);
+        Pubkey::create_program_address(&seeds, &ID) == Ok(key)
+    }
+
+    #[test]
+    fn addresses_use_the_program_seeds() {
+        let (payer, nonce) = (Pubkey::new_unique(), [7; 32]);
+        let game = game_pda(&payer, &nonce);
+        assert!(derives(&[b"game", payer.as_ref(), &nonce], game));
+        assert!(derives(&[b"escrow", game.0.as_ref()], escrow_pda(&game.0)));
+        assert!(derives(&[b"vault", game.0.as_ref()], vault_pda(&game.0)));
+        assert!(derives(&[b"config"], config_pda()));
+
+        // every creator and nonce gets a game of its own
+        assert_ne!(game_pda(&payer, &[8; 32]).0, game.0);
+        assert_ne!(game_pda(&Pubkey::new_unique(), &nonce).0, game.0);
+        assert_ne!(escrow_pda(&game.0).0, vault_pda(&game.0).0);
+    }
+
+    #[test]
+    fn create_game_uses_the_derived_accounts() {
+        let (payer, nonce) = (Pubkey::new_unique(), [3; 32]);
+        let game = game_pda(&payer, &nonce).0;
+        let created = ix::create_game(&payer, nonce, 10, GameConfig::default(), None);
+        let keys: Vec<Pubkey> = created.accounts.iter().map(|m| m.pubkey).collect();
+        assert_eq!(keys[..3], [game, config_pda().0, escrow_pda(&game).0]);
+
+        let joined = ix::join_game(&game, &Pubkey::new_unique(), None);
+        assert_eq!(joined.accounts[0].pubkey, game);
+        assert_eq!(joined.accounts[1].pubkey, escrow_pda(&game).0);
+    }
+}
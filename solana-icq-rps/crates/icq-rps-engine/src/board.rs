@@ -0,0 +1,1459 @@
+use crate::{
+    rps_choice, Choice, Owner, Piece, RuleError, Rules, Side, StalemateRule, TieBreakMode,
+    ARMY_KINDS, ARMY_PIECES, MAX_CELLS, NOT_SET, WEAPON_KINDS,
+};
+
+/// A tie-break in progress between the pieces on `from` and `to`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tie {
+    pub from: u8,
+    pub to: u8,
+    /// Weapon rounds already tied in this tie-break.
+    pub round: u8,
+}
+
+/// Why a game ended.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EndReason {
+    CapturedFlag,
+    FlagWalkedIntoTrap,
+    FlagWalkedIntoBomb,
+    NoPiecesLeft,
+    NoLegalMoves,
+    /// A revealed piece proved the lineup broke the rules.
+    IllegalLineup,
+}
+
+impl EndReason {
+    /// The reason string carried by the program's `GameOver` event.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CapturedFlag => "captured_flag",
+            Self::FlagWalkedIntoTrap => "flag_walked_into_trap",
+            Self::FlagWalkedIntoBomb => "flag_walked_into_bomb",
+            Self::NoPiecesLeft => "no_pieces_left",
+            Self::NoLegalMoves => "no_legal_moves",
+            Self::IllegalLineup => "illegal_lineup",
+        }
+    }
+}
+
+/// A finished game; `winner` is `None` for a draw.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GameEnd {
+    pub winner: Option<Side>,
+    pub reason: EndReason,
+}
+
+/// A battle between two known pieces.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BattleReport {
+    pub from: u8,
+    pub to: u8,
+    pub attacker: Piece,
+    pub defender: Piece,
+    /// 1 if the attacker won, -1 if it lost, 0 if both were removed.
+    pub outcome: i8,
+    /// The defender was a bomb and went off.
+    pub bomb: bool,
+    /// The move counted and the turn was handed over; false when the battle
+    /// itself decided the game.
+    pub turn_passed: bool,
+    pub game_over: Option<GameEnd>,
+}
+
+/// What became of a battle once both pieces were known.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Battle {
+    Fought(BattleReport),
+    /// Equal pieces: a weapon tie-break is now pending.
+    Tie {
+        from: u8,
+        to: u8,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveOutcome {
+    /// The piece stepped onto an empty cell.
+    Moved { game_over: Option<GameEnd> },
+    /// The piece attacked; the battle is resolved right away when both
+    /// pieces are already known, otherwise it waits for reveals.
+    Attacked { resolved: Option<Battle> },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RevealOutcome {
+    /// The other piece of the battle is still hidden.
+    Waiting,
+    Resolved(Battle),
+    /// The piece could not legally be where it was; the revealer forfeits.
+    Illegal(GameEnd),
+}
+
+/// A settled tie-break; `outcome` is from player0's point of view.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TieEnd {
+    pub outcome: i8,
+    pub choices: [Choice; 2],
+    pub game_over: Option<GameEnd>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeaponOutcome {
+    /// The opponent has not revealed a weapon yet.
+    Waiting,
+    /// `round` tied and is played again.
+    Replay {
+        round: u8,
+        choices: [Choice; 2],
+    },
+    Resolved {
+        round: u8,
+        tie: TieEnd,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DraftOutcome {
+    pub left_in_pool: u16,
+    /// Both armies are full.
+    pub done: bool,
+}
+
+/// Everything the rules look at while a game is played. Per-side arrays are
+/// indexed by `Side::idx`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Board {
+    pub rules: Rules,
+
+    // only the first `rules.cells()` entries are on the board
+    pub owner: [Owner; MAX_CELLS],
+    pub piece: [Piece; MAX_CELLS],
+    // the spawn cell each piece came from
+    pub origin: [u8; MAX_CELLS],
+    pub moved: [bool; MAX_CELLS],
+    // hidden pieces that moved like a scout
+    pub scouted: [bool; MAX_CELLS],
+
+    pub live: [u16; 2],
+    pub flag_pos: [u8; 2],
+
+    // declared army per kind, and how many of each have been revealed
+    pub army: [[u8; ARMY_KINDS]; 2],
+    pub revealed: [[u8; ARMY_KINDS]; 2],
+    // what is left of the draft pool
+    pub pool: [u8; ARMY_KINDS],
+
+    pub turn: Side,
+    // battle waiting for both pieces to be revealed
+    pub battle: Option<(u8, u8)>,
+    pub tie: Option<Tie>,
+    pub choice: [Option<Choice>; 2],
+    // tie-break weapons left when `rules.limited_weapons` is on
+    pub weapon_stock: [[u8; WEAPON_KINDS]; 2],
+}
+
+impl Board {
+    /// An empty board with the terrain of `rules` laid out.
+    pub fn new(rules: Rules) -> Self {
+        let mut owner = [Owner::None; MAX_CELLS];
+        for (idx, o) in owner.iter_mut().enumerate().take(rules.cells()) {
+            if rules.is_blocked(idx) {
+                *o = Owner::Blocked;
+            }
+        }
+        Self {
+            rules,
+            owner,
+            piece: [Piece::Empty; MAX_CELLS],
+            origin: [NOT_SET; MAX_CELLS],
+            moved: [false; MAX_CELLS],
+            scouted: [false; MAX_CELLS],
+            live: [0; 2],
+            flag_pos: [NOT_SET; 2],
+            army: [[0; ARMY_KINDS]; 2],
+            revealed: [[0; ARMY_KINDS]; 2],
+            pool: rules.army.pool,
+            turn: Side::P0,
+            battle: None,
+            tie: None,
+            choice: [None; 2],
+            weapon_stock: [rules.weapon_stock; 2],
+        }
+    }
+
+    /// The side whose piece stands on `idx`, if any.
+    pub fn side_at(&self, idx: u8) -> Option<Side> {
+        match self.owner[idx as usize] {
+            Owner::P0 => Some(Side::P0),
+            Owner::P1 => Some(Side::P1),
+            _ => None,
+        }
+    }
+
+    /// Takes one piece from the shared pool for `side`. The caller decides
+    /// whose pick it is.
+    pub fn draft_pick(&mut self, side: Side, piece: Piece) -> Result<DraftOutcome, RuleError> {
+        let Some(k) = piece.army_slot() else {
+            return Err(RuleError::PieceNotInPool);
+        };
+        if self.pool[k] == 0 {
+            return Err(RuleError::PieceNotInPool);
+        }
+        let rules = self.rules.army;
+        let mut mine = self.army[side.idx()];
+        let theirs = self.army[side.other().idx()];
+        if mine[k] >= rules.max[k] {
+            let (count, max) = (mine[k].saturating_add(1), rules.max[k]);
+            return Err(RuleError::TooManyPieces { piece, count, max });
+        }
+        mine[k] += 1;
+        let mut pool = self.pool;
+        pool[k] -= 1;
+        if !self.draft_still_completable(&pool, &mine, &theirs) {
+            return Err(RuleError::DraftPickBlocked);
+        }
+
+        self.pool = pool;
+        self.army[side.idx()] = mine;
+        let full = |army: &[u8; ARMY_KINDS]| army.iter().sum::<u8>() >= rules.size;
+        Ok(DraftOutcome {
+            left_in_pool: pool.iter().map(|&c| c as u16).sum(),
+            done: full(&self.army[0]) && full(&self.army[1]),
+        })
+    }
+
+    /// A pick must leave both players able to reach their minimums and a
+    /// full army, so nobody can be starved of a required piece.
+    fn draft_still_completable(
+        &self,
+        pool: &[u8; ARMY_KINDS],
+        mine: &[u8; ARMY_KINDS],
+        theirs: &[u8; ARMY_KINDS],
+    ) -> bool {
+        let rules = &self.rules.army;
+        let unmet = |army: &[u8; ARMY_KINDS], k: usize| rules.min[k].saturating_sub(army[k]);
+        let open = |army: &[u8; ARMY_KINDS]| {
+            (rules.size as u32).saturating_sub(army.iter().map(|&c| c as u32).sum())
+        };
+
+        let mine_open = open(mine);
+        let mine_unmet: u32 = (0..ARMY_KINDS).map(|k| unmet(mine, k) as u32).sum();
+        let pool_left: u32 = pool.iter().map(|&c| c as u32).sum();
+
+        mine_unmet <= mine_open
+            && pool_left >= mine_open + open(theirs)
+            && (0..ARMY_KINDS).all(|k| pool[k] >= unmet(mine, k) + unmet(theirs, k))
+    }
+
+    /// Places a hidden lineup of the declared `army` on `positions`. Only
+    /// occupied cells are known; piece kinds stay secret until revealed.
+    pub fn place_lineup(
+        &mut self,
+        side: Side,
+        positions: &[u8],
+        army: &[u8; ARMY_KINDS],
+    ) -> Result<(), RuleError> {
+        if positions.is_empty() {
+            return Err(RuleError::LineupPositionsEmpty);
+        }
+        // The composition is public; reveals are later held to it.
+        self.rules.army.check(army)?;
+        if self.rules.army.draft && *army != self.army[side.idx()] {
+            return Err(RuleError::ArmyNotDrafted);
+        }
+        if positions.len() != army.iter().map(|&c| c as usize).sum::<usize>() {
+            return Err(RuleError::ArmySizeMismatch);
+        }
+
+        let mut taken = [false; MAX_CELLS];
+        for &idx in positions {
+            self.rules.check_cell(idx)?;
+            let cell = idx as usize;
+            if self.owner[cell] == Owner::Blocked {
+                return Err(RuleError::CellBlocked);
+            }
+            if taken[cell] || self.owner[cell] != Owner::None || self.piece[cell] != Piece::Empty {
+                return Err(RuleError::CellTaken);
+            }
+            if !self.rules.is_spawn(side, idx) {
+                return Err(RuleError::BadRow(side));
+            }
+            taken[cell] = true;
+        }
+
+        for &idx in positions {
+            let cell = idx as usize;
+            self.owner[cell] = side.owner();
+            self.piece[cell] = Piece::Unknown;
+            self.origin[cell] = idx;
+        }
+        let live = &mut self.live[side.idx()];
+        *live = live.saturating_add(positions.len() as u16);
+        self.army[side.idx()] = *army;
+        Ok(())
+    }
+
+    /// Moves or attacks with the piece on `from`. Hidden pieces may move; a
+    /// trap that moved, or a non-scout that moved like one, is caught when it
+    /// is revealed.
+    pub fn move_piece(&mut self, side: Side, from: u8, to: u8) -> Result<MoveOutcome, RuleError> {
+        if self.tie.is_some() {
+            return Err(RuleError::TieInProgress);
+        }
+        if self.battle.is_some() {
+            return Err(RuleError::BattleInProgress);
+        }
+        self.rules.check_cell(from)?;
+        self.rules.check_cell(to)?;
+        if side != self.turn {
+            return Err(RuleError::NotYourTurn);
+        }
+        let long_move = self.check_step(side, from, to)?;
+
+        let attacker = self.piece[from as usize];
+        if self.owner[to as usize] == Owner::None {
+            self.move_cell(from, to);
+            if long_move && attacker == Piece::Unknown {
+                self.scouted[to as usize] = true;
+            }
+            if attacker == Piece::Flag {
+                self.flag_pos[side.idx()] = to;
+            }
+            return Ok(MoveOutcome::Moved {
+                game_over: self.end_turn(),
+            });
+        }
+
+        self.battle = Some((from, to));
+        let defender = self.piece[to as usize];
+        let known = attacker != Piece::Unknown && defender != Piece::Unknown;
+        Ok(MoveOutcome::Attacked {
+            resolved: known.then(|| self.resolve_battle(from, to)),
+        })
+    }
+
+    /// Every `(from, to)` the player to move may pass to `move_piece`, in
+    /// cell order. Empty while a battle or tie-break is pending.
+    pub fn legal_moves(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        let idle = self.battle.is_none() && self.tie.is_none();
+        self.moves_of(self.turn).filter(move |_| idle)
+    }
+
+    /// `legal_moves` as the player to move sees them: `known` holds the
+    /// piece that spawned on each cell, so their own hidden traps and
+    /// immobile flags stay put and only scouts move more than one cell.
+    /// Cells left `Unknown` in `known` keep the public rules.
+    pub fn legal_moves_knowing<'a>(
+        &'a self,
+        known: &'a [Piece; MAX_CELLS],
+    ) -> impl Iterator<Item = (u8, u8)> + 'a {
+        self.legal_moves().filter(move |&(from, to)| {
+            let piece = match self.piece[from as usize] {
+                Piece::Unknown => known[self.origin[from as usize] as usize],
+                shown => shown,
+            };
+            self.check_step_as(self.turn, from, to, piece).is_ok()
+        })
+    }
+
+    fn moves_of(&self, side: Side) -> impl Iterator<Item = (u8, u8)> + '_ {
+        let cells = self.rules.cells() as u8;
+        let r = &self.rules;
+        (0..cells)
+            .filter(move |&from| self.owner[from as usize] == side.owner())
+            .flat_map(move |from| {
+                (0..cells)
+                    .filter(move |&to| r.x(to) == r.x(from) || r.y(to) == r.y(from))
+                    .filter(move |&to| self.check_step(side, from, to).is_ok())
+                    .map(move |to| (from, to))
+            })
+    }
+
+    /// The piece rules of a move, whoever's turn it is. Returns whether the
+    /// move goes further than one cell.
+    fn check_step(&self, side: Side, from: u8, to: u8) -> Result<bool, RuleError> {
+        self.check_step_as(side, from, to, self.piece[from as usize])
+    }
+
+    /// `check_step` for the piece on `from` taken to be `attacker`.
+    fn check_step_as(
+        &self,
+        side: Side,
+        from: u8,
+        to: u8,
+        attacker: Piece,
+    ) -> Result<bool, RuleError> {
+        if self.owner[from as usize] != side.owner() {
+            return Err(RuleError::InvalidMove);
+        }
+        if attacker == Piece::Empty || attacker.is_static() {
+            return Err(RuleError::InvalidMove);
+        }
+        // the declared army tells when every hidden piece left is immobile
+        if attacker == Piece::Unknown && self.hidden_movers(side) == 0 {
+            return Err(RuleError::InvalidMove);
+        }
+        if attacker == Piece::Flag && !self.rules.flag_can_move {
+            return Err(RuleError::FlagCannotMove);
+        }
+        let long_move = !self.adjacent(from, to);
+        if long_move
+            && (!matches!(attacker, Piece::Scout | Piece::Unknown) || !self.clear_line(from, to))
+        {
+            return Err(RuleError::InvalidMove);
+        }
+        match self.owner[to as usize] {
+            Owner::Blocked => Err(RuleError::CellBlocked),
+            dest if dest == side.owner() => Err(RuleError::CannotStackOwnPiece),
+            _ => Ok(long_move),
+        }
+    }
+
+    /// Checks that `side` may reveal the piece on `idx` and returns the spawn
+    /// cell its lineup commitment covers.
+    pub fn revealable(&self, side: Side, idx: u8) -> Result<u8, RuleError> {
+        let Some((from, to)) = self.battle else {
+            return Err(RuleError::NoBattlePending);
+        };
+        if (idx != from && idx != to) || self.owner[idx as usize] != side.owner() {
+            return Err(RuleError::NotInBattle);
+        }
+        if self.piece[idx as usize] != Piece::Unknown {
+            return Err(RuleError::PieceAlreadyRevealed);
+        }
+        Ok(self.origin[idx as usize])
+    }
+
+    /// Reveals a piece of the pending battle, settling the battle once both
+    /// pieces are known. The caller has already checked the piece against
+    /// the lineup commitment.
+    pub fn reveal(
+        &mut self,
+        side: Side,
+        idx: u8,
+        piece: Piece,
+    ) -> Result<RevealOutcome, RuleError> {
+        self.revealable(side, idx)?;
+        if !self.is_legal_reveal(side, idx, piece) {
+            return Ok(RevealOutcome::Illegal(GameEnd {
+                winner: Some(side.other()),
+                reason: EndReason::IllegalLineup,
+            }));
+        }
+
+        self.piece[idx as usize] = piece;
+        if let Some(k) = piece.army_slot() {
+            self.revealed[side.idx()][k] += 1;
+        }
+        if piece == Piece::Flag {
+            self.flag_pos[side.idx()] = idx;
+        }
+
+        let Some((from, to)) = self.battle else {
+            return Ok(RevealOutcome::Waiting);
+        };
+        let hidden = |p: Piece| p == Piece::Unknown;
+        if hidden(self.piece[from as usize]) || hidden(self.piece[to as usize]) {
+            return Ok(RevealOutcome::Waiting);
+        }
+        Ok(RevealOutcome::Resolved(self.resolve_battle(from, to)))
+    }
+
+    /// Lineup rules that can only be checked once a piece is public. Revealing
+    /// more of a kind than was declared means the declaration was a lie.
+    pub fn is_legal_reveal(&self, side: Side, idx: u8, piece: Piece) -> bool {
+        let Some(k) = piece.army_slot() else {
+            return false;
+        };
+        if self.revealed[side.idx()][k] >= self.army[side.idx()][k] {
+            return false;
+        }
+        let cell = idx as usize;
+        // the attacker of the pending battle is moving right now
+        let attack = self.battle.filter(|&(from, _)| from == idx);
+        let moved = self.moved[cell] || attack.is_some();
+        let scouted =
+            self.scouted[cell] || attack.is_some_and(|(from, to)| !self.adjacent(from, to));
+        if scouted && piece != Piece::Scout {
+            return false;
+        }
+        match piece {
+            Piece::Flag => self.rules.flag_can_move || !moved,
+            // traps never move, so a trap must still stand on its spawn cell
+            Piece::Trap => !moved && self.rules.is_trap_row(side, self.origin[cell]),
+            Piece::Bomb => !moved,
+            _ => true,
+        }
+    }
+
+    /// Settles the battle between two known pieces.
+    fn resolve_battle(&mut self, from: u8, to: u8) -> Battle {
+        self.battle = None;
+        let me = self.turn;
+        let foe = me.other();
+        let attacker = self.piece[from as usize];
+        let defender = self.piece[to as usize];
+        let report = |outcome, bomb, game_over| BattleReport {
+            from,
+            to,
+            attacker,
+            defender,
+            outcome,
+            bomb,
+            turn_passed: true,
+            game_over,
+        };
+
+        if defender.is_static() {
+            self.clear_cell(from);
+            self.kill(me);
+            // bombs always go off with their attacker, traps only if single-use
+            let bomb = defender == Piece::Bomb;
+            if bomb || self.rules.single_use_traps {
+                self.clear_cell(to);
+                self.kill(foe);
+            }
+            if attacker == Piece::Flag {
+                let reason = if bomb {
+                    EndReason::FlagWalkedIntoBomb
+                } else {
+                    EndReason::FlagWalkedIntoTrap
+                };
+                return Battle::Fought(BattleReport {
+                    turn_passed: false,
+                    ..report(
+                        -1,
+                        bomb,
+                        Some(GameEnd {
+                            winner: Some(foe),
+                            reason,
+                        }),
+                    )
+                });
+            }
+            return Battle::Fought(report(-1, bomb, self.end_turn()));
+        }
+
+        if defender == Piece::Flag {
+            self.clear_cell(from);
+            self.kill(me);
+            self.clear_cell(to);
+            let end = GameEnd {
+                winner: Some(me),
+                reason: EndReason::CapturedFlag,
+            };
+            return Battle::Fought(BattleReport {
+                turn_passed: false,
+                ..report(1, false, Some(end))
+            });
+        }
+
+        let outcome = self.rules.rps(attacker, defender);
+        // a tie-break nobody can reveal a weapon for would never end
+        let mutual = self.rules.tie_break == TieBreakMode::BothRemoved || !self.weapons_left();
+        if outcome == 0 && mutual {
+            self.clear_cell(from);
+            self.clear_cell(to);
+            self.kill(me);
+            self.kill(foe);
+            return Battle::Fought(report(0, false, self.end_turn()));
+        }
+        if outcome == 0 {
+            self.tie = Some(Tie { from, to, round: 0 });
+            self.choice = [None; 2];
+            return Battle::Tie { from, to };
+        }
+
+        if outcome == 1 {
+            self.kill(foe);
+            self.clear_cell(to);
+            self.move_cell(from, to);
+            if attacker == Piece::Flag {
+                self.flag_pos[me.idx()] = to;
+            }
+        } else {
+            self.clear_cell(from);
+            self.kill(me);
+        }
+        Battle::Fought(report(outcome, false, self.end_turn()))
+    }
+
+    /// Checks that `side` may commit to a weapon for the pending tie-break.
+    pub fn check_commit(&self, side: Side) -> Result<(), RuleError> {
+        if self.tie.is_none() {
+            return Err(RuleError::NoTiePending);
+        }
+        if self.rules.limited_weapons && self.weapon_stock[side.idx()] == [0; WEAPON_KINDS] {
+            return Err(RuleError::WeaponExhausted);
+        }
+        Ok(())
+    }
+
+    /// Whether both sides still have a weapon to reveal in a tie-break.
+    fn weapons_left(&self) -> bool {
+        !self.rules.limited_weapons
+            || self
+                .weapon_stock
+                .iter()
+                .all(|stock| stock.iter().any(|&n| n > 0))
+    }
+
+    /// Records the weapon `side` revealed for the pending tie-break and plays
+    /// the round once both weapons are in. The caller has already checked
+    /// the weapon against its commitment.
+    pub fn reveal_weapon(
+        &mut self,
+        side: Side,
+        choice: Choice,
+    ) -> Result<WeaponOutcome, RuleError> {
+        let Some(tie) = self.tie else {
+            return Err(RuleError::NoTiePending);
+        };
+        let Some(weapon) = choice.weapon().filter(|&w| self.rules.weapons.allows(w)) else {
+            return Err(RuleError::InvalidChoice);
+        };
+        if self.choice[side.idx()].is_some() {
+            return Err(RuleError::AlreadyChose);
+        }
+        if self.rules.limited_weapons {
+            let stock = &mut self.weapon_stock[side.idx()][weapon as usize];
+            if *stock == 0 {
+                return Err(RuleError::WeaponExhausted);
+            }
+            *stock -= 1;
+        }
+        self.choice[side.idx()] = Some(choice);
+
+        let [Some(c0), Some(c1)] = self.choice else {
+            return Ok(WeaponOutcome::Waiting);
+        };
+        let outcome = rps_choice(c0, c1);
+        let replay = outcome == 0
+            && self.rules.tie_break == TieBreakMode::Repeat
+            && tie.round + 1 < self.rules.max_tie_rounds
+            && self.weapons_left();
+        if replay {
+            self.tie = Some(Tie {
+                round: tie.round + 1,
+                ..tie
+            });
+            self.choice = [None; 2];
+            return Ok(WeaponOutcome::Replay {
+                round: tie.round,
+                choices: [c0, c1],
+            });
+        }
+        Ok(WeaponOutcome::Resolved {
+            round: tie.round,
+            tie: self.resolve_tie(tie, outcome),
+        })
+    }
+
+    /// Hands the pending tie-break to `winner`, whose opponent never revealed.
+    pub fn forfeit_tie(&mut self, winner: Side) -> Result<TieEnd, RuleError> {
+        let Some(tie) = self.tie else {
+            return Err(RuleError::NoTiePending);
+        };
+        let outcome = if winner == Side::P0 { 1 } else { -1 };
+        Ok(self.resolve_tie(tie, outcome))
+    }
+
+    /// Applies a tie-break result; `outcome` is from player0's point of view.
+    fn resolve_tie(&mut self, tie: Tie, outcome: i8) -> TieEnd {
+        let choices = self.choice.map(|c| c.unwrap_or(Choice::None));
+        let me = self.turn;
+        let foe = me.other();
+        let attacker = self.piece[tie.from as usize];
+        let attacker_wins = match me {
+            Side::P0 => outcome == 1,
+            Side::P1 => outcome == -1,
+        };
+
+        if attacker_wins {
+            self.kill(foe);
+            self.clear_cell(tie.to);
+            self.move_cell(tie.from, tie.to);
+            if attacker == Piece::Flag {
+                self.flag_pos[me.idx()] = tie.to;
+            }
+        } else if outcome == 0 {
+            self.clear_cell(tie.from);
+            self.clear_cell(tie.to);
+            self.kill(me);
+            self.kill(foe);
+        } else {
+            self.clear_cell(tie.from);
+            self.kill(me);
+        }
+
+        self.tie = None;
+        self.choice = [None; 2];
+        TieEnd {
+            outcome,
+            choices,
+            game_over: self.end_turn(),
+        }
+    }
+
+    /// Ends the game if a side ran out of pieces, otherwise hands the turn
+    /// over and applies the stalemate rule if the next player is stuck. When
+    /// the last pieces of both sides fall together nobody wins.
+    fn end_turn(&mut self) -> Option<GameEnd> {
+        if self.live.contains(&0) {
+            let winner = match self.live {
+                [0, 0] => None,
+                [0, _] => Some(Side::P1),
+                _ => Some(Side::P0),
+            };
+            return Some(GameEnd {
+                winner,
+                reason: EndReason::NoPiecesLeft,
+            });
+        }
+        self.turn = self.turn.other();
+
+        if self.has_legal_move(self.turn) {
+            return None;
+        }
+        let winner = match self.rules.stalemate {
+            StalemateRule::Draw => None,
+            StalemateRule::Loss => Some(self.turn.other()),
+        };
+        Some(GameEnd {
+            winner,
+            reason: EndReason::NoLegalMoves,
+        })
+    }
+
+    /// Whether the given side has any piece that can step somewhere. Hidden
+    /// pieces count as movable while the declared army still hides a piece
+    /// of a moving kind, since only their owner knows which one it is.
+    pub fn has_legal_move(&self, side: Side) -> bool {
+        self.moves_of(side).next().is_some()
+    }
+
+    /// How many of `side`'s hidden pieces are of a kind that moves: the
+    /// declared army minus what has been revealed of it.
+    pub fn hidden_movers(&self, side: Side) -> u16 {
+        let (army, revealed) = (&self.army[side.idx()], &self.revealed[side.idx()]);
+        ARMY_PIECES
+            .iter()
+            .enumerate()
+            .filter(|&(_, &p)| !p.is_static() && (p != Piece::Flag || self.rules.flag_can_move))
+            .map(|(k, _)| army[k].saturating_sub(revealed[k]) as u16)
+            .sum()
+    }
+
+    pub fn adjacent(&self, from: u8, to: u8) -> bool {
+        let r = &self.rules;
+        let dx = r.x(from).abs_diff(r.x(to));
+        let dy = r.y(from).abs_diff(r.y(to));
+        dx + dy == 1
+    }
+
+    /// Whether `from` and `to` share a row or column with only empty cells
+    /// between them.
+    fn clear_line(&self, from: u8, to: u8) -> bool {
+        let r = &self.rules;
+        let step: i16 = if from == to {
+            return false;
+        } else if r.y(from) == r.y(to) {
+            1
+        } else if r.x(from) == r.x(to) {
+            r.width as i16
+        } else {
+            return false;
+        };
+        let step = if to > from { step } else { -step };
+        let mut idx = from as i16 + step;
+        while idx != to as i16 {
+            if self.owner[idx as usize] != Owner::None {
+                return false;
+            }
+            idx += step;
+        }
+        true
+    }
+
+    fn kill(&mut self, side: Side) {
+        let live = &mut self.live[side.idx()];
+        *live = live.saturating_sub(1);
+    }
+
+    fn clear_cell(&mut self, idx: u8) {
+        let idx = idx as usize;
+        self.owner[idx] = Owner::None;
+        self.piece[idx] = Piece::Empty;
+        self.origin[idx] = NOT_SET;
+        self.moved[idx] = false;
+        self.scouted[idx] = false;
+    }
+
+    /// Moves whatever stands on `from` onto `to`, including its spawn origin.
+    fn move_cell(&mut self, from: u8, to: u8) {
+        let (f, t) = (from as usize, to as usize);
+        self.owner[t] = self.owner[f];
+        self.piece[t] = self.piece[f];
+        self.origin[t] = self.origin[f];
+        self.scouted[t] = self.scouted[f];
+        self.clear_cell(from);
+        self.moved[t] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Weapon;
+
+    /// Puts `piece` for `side` on `idx` as if its lineup had been placed
+    /// there, face down unless `shown`.
+    fn place(b: &mut Board, side: Side, idx: u8, piece: Piece, shown: bool) {
+        let cell = idx as usize;
+        let k = piece.army_slot().unwrap();
+        b.owner[cell] = side.owner();
+        b.piece[cell] = if shown { piece } else { Piece::Unknown };
+        b.origin[cell] = idx;
+        b.army[side.idx()][k] += 1;
+        if shown {
+            b.revealed[side.idx()][k] += 1;
+        }
+        b.live[side.idx()] += 1;
+        if piece == Piece::Flag && shown {
+            b.flag_pos[side.idx()] = idx;
+        }
+    }
+
+    fn at(b: &Board, x: u8, y: u8) -> u8 {
+        b.rules.cell_at(x, y).unwrap()
+    }
+
+    #[test]
+    fn hidden_trap_and_fixed_flag_cannot_move() {
+        let mut b = Board::new(Rules {
+            flag_can_move: false,
+            ..Rules::default()
+        });
+        let (flag, trap, rock) = (at(&b, 3, 5), at(&b, 2, 4), at(&b, 3, 0));
+        place(&mut b, Side::P0, flag, Piece::Flag, false);
+        place(&mut b, Side::P0, trap, Piece::Trap, false);
+        place(&mut b, Side::P1, rock, Piece::Rock, false);
+
+        assert_eq!(b.hidden_movers(Side::P0), 0);
+        assert!(!b.has_legal_move(Side::P0));
+        assert_eq!(
+            b.move_piece(Side::P0, trap, at(&b, 2, 3)),
+            Err(RuleError::InvalidMove)
+        );
+
+        // p1's move hands the turn to a side that is stuck
+        b.turn = Side::P1;
+        let outcome = b.move_piece(Side::P1, rock, at(&b, 3, 1)).unwrap();
+        assert_eq!(
+            outcome,
+            MoveOutcome::Moved {
+                game_over: Some(GameEnd {
+                    winner: Some(Side::P1),
+                    reason: EndReason::NoLegalMoves,
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn any_hidden_mover_keeps_hidden_pieces_movable() {
+        let mut b = Board::new(Rules {
+            flag_can_move: false,
+            ..Rules::default()
+        });
+        let (flag, trap, rock) = (at(&b, 3, 5), at(&b, 2, 4), at(&b, 0, 5));
+        place(&mut b, Side::P0, flag, Piece::Flag, false);
+        place(&mut b, Side::P0, trap, Piece::Trap, false);
+        place(&mut b, Side::P0, rock, Piece::Rock, false);
+
+        assert_eq!(b.hidden_movers(Side::P0), 1);
+        // the owner may bluff with any hidden piece; a trap that moved is
+        // caught at its reveal
+        assert!(b.move_piece(Side::P0, trap, at(&b, 2, 3)).is_ok());
+    }
+
+    /// A board holding only the given pieces, all of them known.
+    fn last_pieces(rules: Rules, extra: &[(Side, u8, u8, Piece)]) -> Board {
+        let mut b = Board::new(rules);
+        for &(side, x, y, piece) in extra {
+            let idx = at(&b, x, y);
+            place(&mut b, side, idx, piece, true);
+        }
+        b
+    }
+
+    /// Lets p0 attack from (3, 3) to (3, 2) and returns the fought battle.
+    fn attack_up(b: &mut Board) -> BattleReport {
+        let (from, to) = (at(b, 3, 3), at(b, 3, 2));
+        match b.move_piece(Side::P0, from, to) {
+            Ok(MoveOutcome::Attacked {
+                resolved: Some(Battle::Fought(report)),
+            }) => report,
+            other => panic!("expected a fought battle, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn bomb_taking_the_last_attacker_with_it_is_a_draw() {
+        // no flags, so the bomb and the rock are all that is left
+        let mut b = last_pieces(
+            Rules::default(),
+            &[(Side::P0, 3, 3, Piece::Rock), (Side::P1, 3, 2, Piece::Bomb)],
+        );
+        let report = attack_up(&mut b);
+        assert!(report.bomb);
+        assert_eq!(report.outcome, -1);
+        assert_eq!(b.live, [0, 0]);
+        assert_eq!(
+            report.game_over,
+            Some(GameEnd {
+                winner: None,
+                reason: EndReason::NoPiecesLeft,
+            })
+        );
+    }
+
+    #[test]
+    fn single_use_trap_taking_the_last_attacker_with_it_is_a_draw() {
+        let rules = Rules {
+            single_use_traps: true,
+            ..Rules::default()
+        };
+        let mut b = last_pieces(
+            rules,
+            &[
+                (Side::P0, 3, 3, Piece::Paper),
+                (Side::P1, 3, 2, Piece::Trap),
+            ],
+        );
+        let report = attack_up(&mut b);
+        assert!(!report.bomb);
+        assert_eq!(b.owner[at(&b, 3, 2) as usize], Owner::None);
+        assert_eq!(report.game_over.map(|end| end.winner), Some(None));
+    }
+
+    #[test]
+    fn lasting_trap_wins_over_the_last_attacker() {
+        let mut b = last_pieces(
+            Rules::default(),
+            &[
+                (Side::P0, 3, 3, Piece::Paper),
+                (Side::P1, 3, 2, Piece::Trap),
+            ],
+        );
+        let report = attack_up(&mut b);
+        assert_eq!(b.owner[at(&b, 3, 2) as usize], Owner::P1);
+        assert_eq!(report.game_over.map(|end| end.winner), Some(Some(Side::P1)));
+    }
+
+    #[test]
+    fn equal_last_pieces_removed_together_is_a_draw() {
+        let rules = Rules {
+            tie_break: TieBreakMode::BothRemoved,
+            ..Rules::default()
+        };
+        let mut b = last_pieces(
+            rules,
+            &[(Side::P0, 3, 3, Piece::Rock), (Side::P1, 3, 2, Piece::Rock)],
+        );
+        let report = attack_up(&mut b);
+        assert_eq!(report.outcome, 0);
+        assert_eq!(b.live, [0, 0]);
+        assert_eq!(report.game_over.map(|end| end.winner), Some(None));
+    }
+
+    /// Two known rocks facing each other, p0 to attack, with `stock` for
+    /// both sides.
+    fn rock_standoff(tie_break: TieBreakMode, stock: [u8; WEAPON_KINDS]) -> Board {
+        let mut b = last_pieces(
+            Rules {
+                tie_break,
+                max_tie_rounds: 3,
+                limited_weapons: true,
+                weapon_stock: stock,
+                ..Rules::default()
+            },
+            &[
+                (Side::P0, 3, 3, Piece::Rock),
+                (Side::P0, 0, 5, Piece::Paper),
+                (Side::P1, 3, 2, Piece::Rock),
+                (Side::P1, 0, 0, Piece::Paper),
+            ],
+        );
+        b.weapon_stock = [stock; 2];
+        b
+    }
+
+    #[test]
+    fn spent_weapons_are_refused() {
+        let mut b = rock_standoff(TieBreakMode::Weapons, [1, 1, 1, 0, 0]);
+        let (from, to) = (at(&b, 3, 3), at(&b, 3, 2));
+        assert_eq!(
+            b.move_piece(Side::P0, from, to),
+            Ok(MoveOutcome::Attacked {
+                resolved: Some(Battle::Tie { from, to })
+            })
+        );
+        b.weapon_stock[1][Weapon::Rock as usize] = 0;
+        assert_eq!(
+            b.reveal_weapon(Side::P1, Choice::Rock),
+            Err(RuleError::WeaponExhausted)
+        );
+        assert_eq!(
+            b.reveal_weapon(Side::P1, Choice::Paper),
+            Ok(WeaponOutcome::Waiting)
+        );
+        assert_eq!(b.weapon_stock[1], [0, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn tie_without_weapons_left_removes_both_pieces() {
+        let mut b = rock_standoff(TieBreakMode::Weapons, [1, 0, 0, 0, 0]);
+        b.weapon_stock[1] = [0; WEAPON_KINDS];
+
+        let report = attack_up(&mut b);
+        assert_eq!(report.outcome, 0);
+        assert_eq!(b.tie, None);
+        assert_eq!(b.live, [1, 1]);
+        assert_eq!(b.turn, Side::P1);
+    }
+
+    #[test]
+    fn replay_stops_when_the_stock_runs_out() {
+        let mut b = rock_standoff(TieBreakMode::Repeat, [1, 0, 0, 0, 0]);
+        let (from, to) = (at(&b, 3, 3), at(&b, 3, 2));
+        b.move_piece(Side::P0, from, to).unwrap();
+        assert!(b.tie.is_some());
+        assert_eq!(b.check_commit(Side::P0), Ok(()));
+
+        b.reveal_weapon(Side::P0, Choice::Rock).unwrap();
+        let WeaponOutcome::Resolved { round, tie } =
+            b.reveal_weapon(Side::P1, Choice::Rock).unwrap()
+        else {
+            panic!("a tied round with empty stocks must not be replayed");
+        };
+        assert_eq!((round, tie.outcome), (0, 0));
+        assert_eq!(b.live, [1, 1]);
+        assert_eq!(b.check_commit(Side::P0), Err(RuleError::NoTiePending));
+    }
+
+    #[test]
+    fn commit_needs_a_weapon_in_stock() {
+        let mut b = rock_standoff(TieBreakMode::Weapons, [0, 1, 0, 0, 0]);
+        let (from, to) = (at(&b, 3, 3), at(&b, 3, 2));
+        b.move_piece(Side::P0, from, to).unwrap();
+        b.weapon_stock[0] = [0; WEAPON_KINDS];
+        assert_eq!(b.check_commit(Side::P0), Err(RuleError::WeaponExhausted));
+        assert_eq!(b.check_commit(Side::P1), Ok(()));
+    }
+
+    #[test]
+    fn move_piece_follows_the_piece_rules() {
+        let mut b = last_pieces(
+            Rules {
+                flag_can_move: false,
+                ..Rules::default()
+            },
+            &[
+                (Side::P0, 3, 5, Piece::Flag),
+                (Side::P0, 3, 4, Piece::Rock),
+                (Side::P0, 2, 4, Piece::Trap),
+                (Side::P1, 3, 0, Piece::Flag),
+                (Side::P1, 3, 1, Piece::Paper),
+            ],
+        );
+        let [flag, rock, trap] = [(3, 5), (3, 4), (2, 4)].map(|(x, y)| at(&b, x, y));
+        let [up, far, left] = [(3, 3), (3, 2), (1, 4)].map(|(x, y)| at(&b, x, y));
+
+        assert_eq!(
+            b.move_piece(Side::P1, at(&b, 3, 1), at(&b, 3, 2)),
+            Err(RuleError::NotYourTurn)
+        );
+        assert_eq!(
+            b.move_piece(Side::P0, rock, trap),
+            Err(RuleError::CannotStackOwnPiece)
+        );
+        assert_eq!(
+            b.move_piece(Side::P0, rock, far),
+            Err(RuleError::InvalidMove)
+        );
+        assert_eq!(
+            b.move_piece(Side::P0, trap, left),
+            Err(RuleError::InvalidMove)
+        );
+        assert_eq!(
+            b.move_piece(Side::P0, flag, at(&b, 4, 5)),
+            Err(RuleError::FlagCannotMove)
+        );
+        assert_eq!(b.move_piece(Side::P0, rock, 200), Err(RuleError::BadCell));
+
+        assert_eq!(
+            b.move_piece(Side::P0, rock, up),
+            Ok(MoveOutcome::Moved { game_over: None })
+        );
+        assert_eq!(b.owner[up as usize], Owner::P0);
+        assert_eq!(b.piece[up as usize], Piece::Rock);
+        assert_eq!(b.origin[up as usize], rock);
+        assert!(b.moved[up as usize]);
+        assert_eq!(b.owner[rock as usize], Owner::None);
+        assert_eq!(b.turn, Side::P1);
+    }
+
+    /// A hidden p0 rock next to a hidden p1 piece, p0 to attack.
+    fn hidden_duel(defender: Piece) -> (Board, u8, u8) {
+        let mut b = Board::new(Rules::default());
+        let [flag0, from, to, flag1] = [(3, 5), (3, 3), (3, 2), (3, 0)].map(|(x, y)| at(&b, x, y));
+        place(&mut b, Side::P0, flag0, Piece::Flag, false);
+        place(&mut b, Side::P0, from, Piece::Rock, false);
+        place(&mut b, Side::P1, flag1, Piece::Flag, false);
+        place(&mut b, Side::P1, to, defender, false);
+        (b, from, to)
+    }
+
+    #[test]
+    fn hidden_battle_waits_for_both_reveals() {
+        let (mut b, from, to) = hidden_duel(Piece::Scissors);
+        assert_eq!(
+            b.move_piece(Side::P0, from, to),
+            Ok(MoveOutcome::Attacked { resolved: None })
+        );
+        assert_eq!(
+            b.move_piece(Side::P0, from, to),
+            Err(RuleError::BattleInProgress)
+        );
+        assert_eq!(
+            b.reveal(Side::P1, from, Piece::Rock),
+            Err(RuleError::NotInBattle)
+        );
+        assert_eq!(
+            b.reveal(Side::P0, from, Piece::Rock),
+            Ok(RevealOutcome::Waiting)
+        );
+        assert_eq!(
+            b.reveal(Side::P0, from, Piece::Rock),
+            Err(RuleError::PieceAlreadyRevealed)
+        );
+
+        let Ok(RevealOutcome::Resolved(Battle::Fought(report))) =
+            b.reveal(Side::P1, to, Piece::Scissors)
+        else {
+            panic!("both pieces are known, the battle must be fought");
+        };
+        assert_eq!(report.outcome, 1);
+        assert_eq!(b.owner[to as usize], Owner::P0);
+        assert_eq!(b.live, [2, 1]);
+        assert_eq!(b.revealed[1][Piece::Scissors.army_slot().unwrap()], 1);
+        assert_eq!(
+            b.reveal(Side::P1, to, Piece::Rock),
+            Err(RuleError::NoBattlePending)
+        );
+    }
+
+    #[test]
+    fn reveal_that_breaks_the_lineup_forfeits() {
+        // the declared army holds a single rock
+        let (mut b, from, to) = hidden_duel(Piece::Rock);
+        b.move_piece(Side::P0, from, to).unwrap();
+        let forfeit = Ok(RevealOutcome::Illegal(GameEnd {
+            winner: Some(Side::P0),
+            reason: EndReason::IllegalLineup,
+        }));
+        assert_eq!(b.reveal(Side::P1, to, Piece::Paper), forfeit);
+        // a trap never leaves its cell, nor attacks
+        let (mut b, from, to) = hidden_duel(Piece::Rock);
+        b.army[0][Piece::Trap.army_slot().unwrap()] = 1;
+        b.move_piece(Side::P0, from, to).unwrap();
+        assert_eq!(
+            b.reveal(Side::P0, from, Piece::Trap),
+            Ok(RevealOutcome::Illegal(GameEnd {
+                winner: Some(Side::P1),
+                reason: EndReason::IllegalLineup,
+            }))
+        );
+    }
+
+    #[test]
+    fn capturing_the_flag_wins_at_once() {
+        let mut b = last_pieces(
+            Rules::default(),
+            &[
+                (Side::P0, 3, 3, Piece::Paper),
+                (Side::P0, 3, 5, Piece::Flag),
+                (Side::P1, 3, 2, Piece::Flag),
+                (Side::P1, 0, 0, Piece::Rock),
+            ],
+        );
+        let report = attack_up(&mut b);
+        assert_eq!(report.outcome, 1);
+        assert!(!report.turn_passed);
+        assert_eq!(
+            report.game_over,
+            Some(GameEnd {
+                winner: Some(Side::P0),
+                reason: EndReason::CapturedFlag,
+            })
+        );
+    }
+
+    #[test]
+    fn flag_walking_into_a_trap_loses() {
+        let mut b = last_pieces(
+            Rules::default(),
+            &[
+                (Side::P0, 3, 3, Piece::Flag),
+                (Side::P0, 0, 5, Piece::Rock),
+                (Side::P1, 3, 2, Piece::Trap),
+                (Side::P1, 0, 0, Piece::Rock),
+            ],
+        );
+        let report = attack_up(&mut b);
+        assert_eq!(report.outcome, -1);
+        assert_eq!(
+            report.game_over,
+            Some(GameEnd {
+                winner: Some(Side::P1),
+                reason: EndReason::FlagWalkedIntoTrap,
+            })
+        );
+    }
+
+    #[test]
+    fn repeat_mode_replays_tied_rounds() {
+        let mut b = last_pieces(
+            Rules {
+                tie_break: TieBreakMode::Repeat,
+                max_tie_rounds: 2,
+                ..Rules::default()
+            },
+            &[
+                (Side::P0, 3, 3, Piece::Rock),
+                (Side::P0, 0, 5, Piece::Paper),
+                (Side::P1, 3, 2, Piece::Rock),
+                (Side::P1, 0, 0, Piece::Paper),
+            ],
+        );
+        let (from, to) = (at(&b, 3, 3), at(&b, 3, 2));
+        assert_eq!(
+            b.move_piece(Side::P0, from, to),
+            Ok(MoveOutcome::Attacked {
+                resolved: Some(Battle::Tie { from, to })
+            })
+        );
+        assert_eq!(
+            b.reveal_weapon(Side::P0, Choice::Lizard),
+            Err(RuleError::InvalidChoice)
+        );
+        b.reveal_weapon(Side::P0, Choice::Rock).unwrap();
+        assert_eq!(
+            b.reveal_weapon(Side::P0, Choice::Paper),
+            Err(RuleError::AlreadyChose)
+        );
+        assert_eq!(
+            b.reveal_weapon(Side::P1, Choice::Rock),
+            Ok(WeaponOutcome::Replay {
+                round: 0,
+                choices: [Choice::Rock, Choice::Rock],
+            })
+        );
+        assert_eq!(b.tie.map(|t| t.round), Some(1));
+
+        // the last allowed round ties too: both pieces go
+        b.reveal_weapon(Side::P0, Choice::Paper).unwrap();
+        let Ok(WeaponOutcome::Resolved { round, tie }) = b.reveal_weapon(Side::P1, Choice::Paper)
+        else {
+            panic!("the last round must settle the tie");
+        };
+        assert_eq!((round, tie.outcome), (1, 0));
+        assert_eq!(b.live, [1, 1]);
+        assert_eq!(b.tie, None);
+    }
+
+    #[test]
+    fn tie_break_winner_takes_the_cell() {
+        let mut b = last_pieces(
+            Rules::default(),
+            &[
+                (Side::P0, 3, 3, Piece::Rock),
+                (Side::P0, 0, 5, Piece::Paper),
+                (Side::P1, 3, 2, Piece::Rock),
+                (Side::P1, 0, 0, Piece::Paper),
+            ],
+        );
+        let (from, to) = (at(&b, 3, 3), at(&b, 3, 2));
+        b.move_piece(Side::P0, from, to).unwrap();
+        b.reveal_weapon(Side::P1, Choice::Scissors).unwrap();
+        let Ok(WeaponOutcome::Resolved { tie, .. }) = b.reveal_weapon(Side::P0, Choice::Rock)
+        else {
+            panic!("a decisive round settles the tie");
+        };
+        assert_eq!(tie.outcome, 1);
+        assert_eq!(tie.choices, [Choice::Rock, Choice::Scissors]);
+        assert_eq!(b.owner[to as usize], Owner::P0);
+        assert_eq!(b.live, [2, 1]);
+        assert_eq!(b.turn, Side::P1);
+    }
+
+    #[test]
+    fn draft_picks_come_from_the_pool() {
+        let mut rules = Rules::default();
+        rules.army.size = 2;
+        rules.army.max = [2, 2, 0, 1, 0, 0, 0, 0, 0];
+        rules.army.draft = true;
+        rules.army.pool = [1, 2, 0, 2, 0, 0, 0, 0, 0];
+        let mut b = Board::new(rules);
+
+        assert_eq!(
+            b.draft_pick(Side::P1, Piece::Scissors),
+            Err(RuleError::PieceNotInPool)
+        );
+        // both players still need a flag and a second piece
+        assert_eq!(
+            b.draft_pick(Side::P1, Piece::Flag),
+            Ok(DraftOutcome {
+                left_in_pool: 4,
+                done: false
+            })
+        );
+        assert_eq!(
+            b.draft_pick(Side::P1, Piece::Flag),
+            Err(RuleError::TooManyPieces {
+                piece: Piece::Flag,
+                count: 2,
+                max: 1
+            })
+        );
+        // taking the last flag would leave p0 without one
+        b.draft_pick(Side::P1, Piece::Rock).unwrap();
+        assert_eq!(b.pool, [0, 2, 0, 1, 0, 0, 0, 0, 0]);
+        b.draft_pick(Side::P0, Piece::Paper).unwrap();
+        assert_eq!(
+            b.draft_pick(Side::P0, Piece::Paper),
+            Err(RuleError::DraftPickBlocked)
+        );
+        assert_eq!(
+            b.draft_pick(Side::P0, Piece::Flag),
+            Ok(DraftOutcome {
+                left_in_pool: 1,
+                done: true
+            })
+        );
+        assert_eq!(b.army[0], [0, 1, 0, 1, 0, 0, 0, 0, 0]);
+    }
+
+    /// What p0 knows of their own lineup: each placed piece on its cell.
+    fn lineup_of(b: &mut Board, pieces: &[(u8, u8, Piece)]) -> [Piece; MAX_CELLS] {
+        let mut known = [Piece::Unknown; MAX_CELLS];
+        for &(x, y, piece) in pieces {
+            let idx = at(b, x, y);
+            place(b, Side::P0, idx, piece, false);
+            known[idx as usize] = piece;
+        }
+        known
+    }
+
+    /// Where the piece on `(x, y)` may go, as p0 knowing `known` sees it.
+    fn targets<'a>(
+        b: &'a Board,
+        known: &'a [Piece; MAX_CELLS],
+        (x, y): (u8, u8),
+    ) -> impl Iterator<Item = (u8, u8)> + 'a {
+        let from = at(b, x, y);
+        b.legal_moves_knowing(known)
+            .filter(move |&(f, _)| f == from)
+            .map(|(_, to)| (b.rules.x(to), b.rules.y(to)))
+    }
+
+    #[test]
+    fn known_lineup_keeps_traps_and_fixed_flags_still() {
+        let mut b = Board::new(Rules {
+            flag_can_move: false,
+            ..Rules::default()
+        });
+        let known = lineup_of(
+            &mut b,
+            &[
+                (0, 5, Piece::Flag),
+                (1, 5, Piece::Trap),
+                (3, 4, Piece::Rock),
+            ],
+        );
+        let enemy = at(&b, 3, 0);
+        place(&mut b, Side::P1, enemy, Piece::Rock, false);
+
+        // to the opponent, any hidden piece might be a scout
+        let (trap, rock) = (at(&b, 1, 5), at(&b, 3, 4));
+        assert!(b.legal_moves().any(|m| m == (trap, at(&b, 1, 4))));
+        assert!(b.legal_moves().any(|m| m == (rock, at(&b, 3, 1))));
+
+        assert!(b.legal_moves_knowing(&known).all(|(from, _)| from == rock));
+        assert!(targets(&b, &known, (3, 4)).eq([(3, 3), (2, 4), (4, 4), (3, 5)]));
+    }
+
+    #[test]
+    fn known_scouts_stop_at_lakes_and_pieces() {
+        let mut b = Board::new(Rules {
+            terrain: crate::Terrain::Lakes,
+            ..Rules::default()
+        });
+        let known = lineup_of(
+            &mut b,
+            &[
+                (1, 4, Piece::Scout),
+                (4, 4, Piece::Rock),
+                (3, 5, Piece::Scout),
+                (6, 5, Piece::Flag),
+            ],
+        );
+        let enemy = at(&b, 3, 1);
+        place(&mut b, Side::P1, enemy, Piece::Rock, false);
+
+        // the lake above and the own rock to the right cut the lines short
+        assert!(targets(&b, &known, (1, 4)).eq([(0, 4), (2, 4), (3, 4), (1, 5)]));
+        // the enemy rock may be attacked but not jumped over
+        assert!(targets(&b, &known, (3, 5)).eq([
+            (3, 1),
+            (3, 2),
+            (3, 3),
+            (3, 4),
+            (0, 5),
+            (1, 5),
+            (2, 5),
+            (4, 5),
+            (5, 5),
+        ]));
+        // the rock steps one cell at a time
+        assert!(targets(&b, &known, (4, 4)).eq([(4, 3), (3, 4), (5, 4), (4, 5)]));
+    }
+
+    #[test]
+    fn no_moves_while_a_battle_or_tie_is_pending() {
+        let (mut b, from, to) = hidden_duel(Piece::Paper);
+        let mut known = [Piece::Unknown; MAX_CELLS];
+        known[from as usize] = Piece::Rock;
+        assert!(b.legal_moves_knowing(&known).next().is_some());
+        b.move_piece(Side::P0, from, to).unwrap();
+        assert_eq!(b.legal_moves().count(), 0);
+        assert_eq!(b.legal_moves_knowing(&known).count(), 0);
+
+        let mut b = last_pieces(
+            Rules::default(),
+            &[
+                (Side::P0, 3, 3, Piece::Rock),
+                (Side::P0, 0, 5, Piece::Paper),
+                (Side::P1, 3, 2, Piece::Rock),
+                (Side::P1, 0, 0, Piece::Paper),
+            ],
+        );
+        b.move_piece(Side::P0, at(&b, 3, 3), at(&b, 3, 2)).unwrap();
+        assert!(b.tie.is_some());
+        assert_eq!(b.legal_moves().count(), 0);
+        assert_eq!(b.legal_moves_knowing(&known).count(), 0);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{Piece, Side};
+
+/// Why a state transition was refused. A refused transition leaves the
+/// board untouched.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RuleError {
+    // rules
+    InvalidBoardSize,
+    InvalidSpawnDepth,
+    InvalidTieRounds,
+    InvalidWeaponStock,
+    InvalidTerrain,
+    InvalidArmyRules,
+
+    // lineups and drafting
+    LineupPositionsEmpty,
+    ArmySizeMismatch,
+    TooFewPieces {
+        piece: Piece,
+        count: u8,
+        min: u8,
+    },
+    TooManyPieces {
+        piece: Piece,
+        count: u8,
+        max: u8,
+    },
+    OverBudget {
+        cost: u32,
+        budget: u16,
+    },
+    ArmyNotDrafted,
+    PieceNotInPool,
+    DraftPickBlocked,
+    /// The cell is outside the spawn zone of `Side`.
+    BadRow(Side),
+
+    // cells and moves
+    BadCell,
+    CellBlocked,
+    CellTaken,
+    NotYourTurn,
+    InvalidMove,
+    FlagCannotMove,
+    CannotStackOwnPiece,
+
+    // battles and tie-breaks
+    BattleInProgress,
+    NoBattlePending,
+    NotInBattle,
+    PieceAlreadyRevealed,
+    TieInProgress,
+    NoTiePending,
+    InvalidChoice,
+    AlreadyChose,
+    WeaponExhausted,
+}
@@ -0,0 +1,25 @@
+//! Game rules of ICQ rock-paper-scissors, free of any Solana or Anchor type.
+//!
+//! The on-chain program keeps accounts, signers, commitments, clocks and
+//! wagers, and hands every state transition to a [`Board`]. Clients that
+//! simulate a game off-chain drive the same `Board`, so they always agree
+//! with the program.
+#![no_std]
+
+pub mod board;
+pub use board::*;
+
+pub mod error;
+pub use error::*;
+
+pub mod piece;
+pub use piece::*;
+
+pub mod rules;
+pub use rules::*;
+
+pub mod weapon;
+pub use weapon::*;
+
+/// Marks an unset cell index, such as the origin of an empty cell.
+pub const NOT_SET: u8 = 255;
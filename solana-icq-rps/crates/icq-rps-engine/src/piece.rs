@@ -0,0 +1,125 @@
+use crate::Weapon;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Piece {
+    Empty = 0,
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+    Flag = 4,
+    Trap = 5,
+    Unknown = 6,
+    Lizard = 7,
+    Spock = 8,
+    /// Moves any number of empty cells in a straight line.
+    Scout = 9,
+    /// Destroys itself together with whatever attacks it.
+    Bomb = 10,
+}
+
+impl From<u8> for Piece {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Rock,
+            2 => Self::Paper,
+            3 => Self::Scissors,
+            4 => Self::Flag,
+            5 => Self::Trap,
+            6 => Self::Unknown,
+            7 => Self::Lizard,
+            8 => Self::Spock,
+            9 => Self::Scout,
+            10 => Self::Bomb,
+            _ => Self::Empty,
+        }
+    }
+}
+
+/// Number of piece kinds a lineup is built from.
+pub const ARMY_KINDS: usize = 9;
+pub const ARMY_PIECES: [Piece; ARMY_KINDS] = [
+    Piece::Rock,
+    Piece::Paper,
+    Piece::Scissors,
+    Piece::Flag,
+    Piece::Trap,
+    Piece::Lizard,
+    Piece::Spock,
+    Piece::Scout,
+    Piece::Bomb,
+];
+
+impl Piece {
+    /// Defensive pieces that never leave their spawn cell.
+    pub fn is_static(self) -> bool {
+        matches!(self, Self::Trap | Self::Bomb)
+    }
+
+    pub fn weapon(self) -> Option<Weapon> {
+        match self {
+            Self::Rock => Some(Weapon::Rock),
+            Self::Paper => Some(Weapon::Paper),
+            Self::Scissors => Some(Weapon::Scissors),
+            Self::Lizard => Some(Weapon::Lizard),
+            Self::Spock => Some(Weapon::Spock),
+            _ => None,
+        }
+    }
+
+    /// Index into per-kind army counts, `None` for non-army values.
+    pub fn army_slot(self) -> Option<usize> {
+        ARMY_PIECES.iter().position(|&p| p == self)
+    }
+}
+
+/// Who a cell belongs to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Owner {
+    None = 0,
+    P0 = 1,
+    P1 = 2,
+    /// Terrain no piece may enter or be placed on.
+    Blocked = 3,
+}
+
+impl From<u8> for Owner {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::P0,
+            2 => Self::P1,
+            3 => Self::Blocked,
+            _ => Self::None,
+        }
+    }
+}
+
+/// One of the two players. Player0 spawns at the bottom and moves first.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Side {
+    P0 = 0,
+    P1 = 1,
+}
+
+impl Side {
+    pub fn other(self) -> Side {
+        match self {
+            Self::P0 => Self::P1,
+            Self::P1 => Self::P0,
+        }
+    }
+
+    pub fn owner(self) -> Owner {
+        match self {
+            Self::P0 => Owner::P0,
+            Self::P1 => Owner::P1,
+        }
+    }
+
+    /// Index into the per-side arrays of a `Board`.
+    pub fn idx(self) -> usize {
+        self as usize
+    }
+}
@@ -0,0 +1,500 @@
+use crate::{
+    duel, Piece, RuleError, Side, Weapon, WeaponSet, ARMY_KINDS, ARMY_PIECES, WEAPONS, WEAPON_KINDS,
+};
+
+pub const MAX_WIDTH: u8 = 10;
+pub const MAX_HEIGHT: u8 = 10;
+pub const MAX_CELLS: usize = (MAX_WIDTH as usize) * (MAX_HEIGHT as usize);
+pub const TERRAIN_BYTES: usize = MAX_CELLS.div_ceil(8);
+
+/// Impassable cells on the board.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Terrain {
+    Open = 0,
+    /// Two lake columns across the rows between the spawn zones.
+    Lakes = 1,
+    /// Cells set in `Rules::blocked`, one bit per cell index.
+    Custom = 2,
+}
+
+impl From<u8> for Terrain {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Lakes,
+            2 => Self::Custom,
+            _ => Self::Open,
+        }
+    }
+}
+
+/// How a battle between two equal pieces is settled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum TieBreakMode {
+    /// Both players pick a weapon.
+    Weapons = 0,
+    /// Both pieces are removed right away.
+    BothRemoved = 1,
+    /// Like `Weapons`, but a tied round is replayed up to
+    /// `Rules::max_tie_rounds` rounds before both pieces are removed.
+    Repeat = 2,
+}
+
+impl From<u8> for TieBreakMode {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::BothRemoved,
+            2 => Self::Repeat,
+            _ => Self::Weapons,
+        }
+    }
+}
+
+/// How a lineup's size is constrained.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum ArmyMode {
+    /// Exactly `size` pieces.
+    Composition = 0,
+    /// Up to `size` pieces whose summed `cost` stays within `budget`.
+    Budget = 1,
+}
+
+impl From<u8> for ArmyMode {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Budget,
+            _ => Self::Composition,
+        }
+    }
+}
+
+/// What happens when the player to move has no legal move left.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum StalemateRule {
+    Loss = 0,
+    Draw = 1,
+}
+
+impl From<u8> for StalemateRule {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Draw,
+            _ => Self::Loss,
+        }
+    }
+}
+
+/// Lineup composition, counted per `ARMY_PIECES` kind. `min == max` pins a
+/// kind to an exact count.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ArmyRules {
+    pub mode: ArmyMode,
+    pub size: u8,
+    pub min: [u8; ARMY_KINDS],
+    pub max: [u8; ARMY_KINDS],
+    // point costs, only used in `ArmyMode::Budget`
+    pub cost: [u8; ARMY_KINDS],
+    pub budget: u16,
+    // players draft their armies in turns from `pool` before the lineups
+    pub draft: bool,
+    pub pool: [u8; ARMY_KINDS],
+}
+
+impl ArmyRules {
+    /// Checks a declared composition, naming the first kind out of range.
+    pub fn check(&self, counts: &[u8; ARMY_KINDS]) -> Result<(), RuleError> {
+        let total: u32 = counts.iter().map(|&c| c as u32).sum();
+        match self.mode {
+            ArmyMode::Composition => {
+                if total != self.size as u32 {
+                    return Err(RuleError::ArmySizeMismatch);
+                }
+            }
+            ArmyMode::Budget => {
+                if total == 0 || total > self.size as u32 {
+                    return Err(RuleError::ArmySizeMismatch);
+                }
+                let cost = self.cost_of(counts);
+                if cost > self.budget as u32 {
+                    return Err(RuleError::OverBudget {
+                        cost,
+                        budget: self.budget,
+                    });
+                }
+            }
+        }
+        for (k, &count) in counts.iter().enumerate() {
+            let piece = ARMY_PIECES[k];
+            if count < self.min[k] {
+                let min = self.min[k];
+                return Err(RuleError::TooFewPieces { piece, count, min });
+            }
+            if count > self.max[k] {
+                let max = self.max[k];
+                return Err(RuleError::TooManyPieces { piece, count, max });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn cost_of(&self, counts: &[u8; ARMY_KINDS]) -> u32 {
+        counts
+            .iter()
+            .zip(&self.cost)
+            .map(|(&n, &c)| n as u32 * c as u32)
+            .sum()
+    }
+
+    fn validate(&self, spawn_cells: usize) -> Result<(), RuleError> {
+        let min: u32 = self.min.iter().map(|&c| c as u32).sum();
+        let max: u32 = self.max.iter().map(|&c| c as u32).sum();
+        let size = self.size as u32;
+        let fits = size > 0 && size as usize <= spawn_cells && min <= size;
+        let sized = match self.mode {
+            ArmyMode::Composition => size <= max,
+            // the mandatory pieces alone must be affordable
+            ArmyMode::Budget => self.budget > 0 && self.cost_of(&self.min) <= self.budget as u32,
+        };
+        let ordered = self.min.iter().zip(&self.max).all(|(lo, hi)| lo <= hi);
+        let draftable = !self.draft || {
+            let pool: u32 = self.pool.iter().map(|&c| c as u32).sum();
+            self.mode == ArmyMode::Composition
+                && pool >= 2 * size
+                && (0..ARMY_KINDS).all(|k| self.pool[k] >= 2 * self.min[k])
+        };
+        // a player has a single flag position
+        let one_flag = Piece::Flag.army_slot().map_or(0, |k| self.max[k]) <= 1;
+        if fits && sized && ordered && draftable && one_flag {
+            Ok(())
+        } else {
+            Err(RuleError::InvalidArmyRules)
+        }
+    }
+}
+
+/// Everything that decides how a game plays, fixed when it is created.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rules {
+    pub width: u8,
+    pub height: u8,
+    /// Rows at each end of the board a player may place pieces on.
+    pub spawn_depth: u8,
+    /// Rows, counted from the inner spawn row, a trap may be placed on.
+    pub trap_rows: u8,
+    pub flag_can_move: bool,
+    /// A trap is removed after it kills its first attacker.
+    pub single_use_traps: bool,
+    pub weapons: WeaponSet,
+    /// What a scout counts as in battles.
+    pub scout_weapon: Weapon,
+    pub terrain: Terrain,
+    pub blocked: [u8; TERRAIN_BYTES],
+    pub army: ArmyRules,
+    pub tie_break: TieBreakMode,
+    pub max_tie_rounds: u8,
+    /// Each player may reveal every weapon only `weapon_stock` times. Once a
+    /// side has nothing left, ties remove both pieces instead.
+    pub limited_weapons: bool,
+    pub weapon_stock: [u8; WEAPON_KINDS],
+    pub stalemate: StalemateRule,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            width: 7,
+            height: 6,
+            spawn_depth: 2,
+            trap_rows: 2,
+            flag_can_move: true,
+            single_use_traps: false,
+            weapons: WeaponSet::Classic,
+            scout_weapon: Weapon::Rock,
+            terrain: Terrain::Open,
+            blocked: [0; TERRAIN_BYTES],
+            // one flag, at most one trap, the rest rock/paper/scissors
+            army: ArmyRules {
+                mode: ArmyMode::Composition,
+                size: 14,
+                min: [0, 0, 0, 1, 0, 0, 0, 0, 0],
+                max: [14, 14, 14, 1, 1, 0, 0, 0, 0],
+                cost: [0; ARMY_KINDS],
+                budget: 0,
+                draft: false,
+                pool: [0; ARMY_KINDS],
+            },
+            tie_break: TieBreakMode::Weapons,
+            max_tie_rounds: 1,
+            limited_weapons: false,
+            weapon_stock: [0; WEAPON_KINDS],
+            stalemate: StalemateRule::Loss,
+        }
+    }
+}
+
+impl Rules {
+    pub fn validate(&self) -> Result<(), RuleError> {
+        if !(2..=MAX_WIDTH).contains(&self.width) || !(3..=MAX_HEIGHT).contains(&self.height) {
+            return Err(RuleError::InvalidBoardSize);
+        }
+        // the two spawn zones must not touch
+        if self.spawn_depth == 0
+            || self.spawn_depth as u16 * 2 >= self.height as u16
+            || self.trap_rows > self.spawn_depth
+        {
+            return Err(RuleError::InvalidSpawnDepth);
+        }
+        if self.tie_break == TieBreakMode::Repeat && self.max_tie_rounds == 0 {
+            return Err(RuleError::InvalidTieRounds);
+        }
+        if self.limited_weapons {
+            let in_play = WEAPONS
+                .iter()
+                .zip(&self.weapon_stock)
+                .all(|(&w, &n)| n == 0 || self.weapons.allows(w));
+            if !in_play || self.weapon_stock.iter().all(|&n| n == 0) {
+                return Err(RuleError::InvalidWeaponStock);
+            }
+        }
+        // lakes need room to walk around them; custom terrain stays out of
+        // the spawn zones so a full army always fits
+        let terrain_ok = match self.terrain {
+            Terrain::Open => self.blocked == [0; TERRAIN_BYTES],
+            Terrain::Lakes => self.width >= 5,
+            Terrain::Custom => (0..MAX_CELLS)
+                .filter(|&i| self.blocked[i / 8] & (1 << (i % 8)) != 0)
+                .all(|i| i < self.cells() && !self.is_spawn_row(self.y(i as u8))),
+        };
+        if !terrain_ok {
+            return Err(RuleError::InvalidTerrain);
+        }
+        // armies may only hold pieces whose weapon is in play
+        let armed = self.weapons.allows(self.scout_weapon)
+            && ARMY_PIECES.iter().enumerate().all(|(k, p)| {
+                let banned = p.weapon().is_some_and(|w| !self.weapons.allows(w));
+                !banned || (self.army.max[k] == 0 && self.army.pool[k] == 0)
+            });
+        if !armed {
+            return Err(RuleError::InvalidArmyRules);
+        }
+        self.army
+            .validate(self.width as usize * self.spawn_depth as usize)
+    }
+
+    pub fn cells(&self) -> usize {
+        (self.width as usize) * (self.height as usize)
+    }
+
+    pub fn x(&self, idx: u8) -> u8 {
+        idx % self.width
+    }
+
+    pub fn y(&self, idx: u8) -> u8 {
+        idx / self.width
+    }
+
+    pub fn cell_at(&self, x: u8, y: u8) -> Result<u8, RuleError> {
+        if x < self.width && y < self.height {
+            Ok(y * self.width + x)
+        } else {
+            Err(RuleError::BadCell)
+        }
+    }
+
+    pub fn check_cell(&self, idx: u8) -> Result<(), RuleError> {
+        if (idx as usize) < self.cells() {
+            Ok(())
+        } else {
+            Err(RuleError::BadCell)
+        }
+    }
+
+    fn is_spawn_row(&self, y: u8) -> bool {
+        y < self.spawn_depth || y >= self.height - self.spawn_depth
+    }
+
+    /// Player1 spawns on the top rows, player0 on the bottom rows.
+    pub fn is_spawn(&self, side: Side, idx: u8) -> bool {
+        let y = self.y(idx);
+        match side {
+            Side::P0 => y >= self.height - self.spawn_depth,
+            Side::P1 => y < self.spawn_depth,
+        }
+    }
+
+    /// Traps may only stand on the `trap_rows` spawn rows closest to the middle.
+    pub fn is_trap_row(&self, side: Side, idx: u8) -> bool {
+        let y = self.y(idx);
+        match side {
+            Side::P0 => y < self.height - self.spawn_depth + self.trap_rows,
+            Side::P1 => y + self.trap_rows >= self.spawn_depth,
+        }
+    }
+
+    pub fn is_blocked(&self, idx: usize) -> bool {
+        match self.terrain {
+            Terrain::Open => false,
+            Terrain::Lakes => {
+                let w = self.width as usize;
+                let (x, y) = (idx % w, (idx / w) as u8);
+                let middle = y >= self.spawn_depth && y < self.height - self.spawn_depth;
+                middle && (x == w / 4 || x == w - 1 - w / 4)
+            }
+            Terrain::Custom => self.blocked[idx / 8] & (1 << (idx % 8)) != 0,
+        }
+    }
+
+    /// The weapon a piece fights with, scouts included.
+    pub fn weapon_of(&self, p: Piece) -> Option<Weapon> {
+        match p {
+            Piece::Scout => Some(self.scout_weapon),
+            _ => p.weapon(),
+        }
+    }
+
+    /// 1 if `attacker` beats `defender`, -1 if it loses, 0 on a tie.
+    pub fn rps(&self, attacker: Piece, defender: Piece) -> i8 {
+        if attacker == defender {
+            return 0;
+        }
+        match (self.weapon_of(attacker), self.weapon_of(defender)) {
+            (Some(a), Some(d)) => duel(a, d),
+            _ => -1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_zones_must_not_touch() {
+        let deep = |spawn_depth| Rules {
+            spawn_depth,
+            ..Rules::default()
+        };
+        assert_eq!(deep(3).validate(), Err(RuleError::InvalidSpawnDepth));
+        // 200 * 2 wraps around a u8
+        assert_eq!(deep(200).validate(), Err(RuleError::InvalidSpawnDepth));
+        assert_eq!(deep(0).validate(), Err(RuleError::InvalidSpawnDepth));
+    }
+
+    #[test]
+    fn large_board_with_deep_spawns_validates() {
+        let rules = Rules {
+            width: MAX_WIDTH,
+            height: MAX_HEIGHT,
+            spawn_depth: 4,
+            ..Rules::default()
+        };
+        assert_eq!(rules.validate(), Ok(()));
+        assert!(rules.is_spawn(Side::P1, rules.cell_at(9, 3).unwrap()));
+        assert!(rules.is_spawn(Side::P0, rules.cell_at(0, 6).unwrap()));
+        assert!(!rules.is_spawn(Side::P0, rules.cell_at(0, 5).unwrap()));
+    }
+
+    #[test]
+    fn army_check_names_the_offending_kind() {
+        let army = Rules::default().army;
+        let flag = Piece::Flag.army_slot().unwrap();
+        let mut counts = [5, 5, 3, 1, 0, 0, 0, 0, 0];
+        assert_eq!(army.check(&counts), Ok(()));
+
+        counts[flag] = 2;
+        counts[0] = 4;
+        assert_eq!(
+            army.check(&counts),
+            Err(RuleError::TooManyPieces {
+                piece: Piece::Flag,
+                count: 2,
+                max: 1
+            })
+        );
+
+        counts[flag] = 0;
+        counts[0] = 6;
+        assert_eq!(
+            army.check(&counts),
+            Err(RuleError::TooFewPieces {
+                piece: Piece::Flag,
+                count: 0,
+                min: 1
+            })
+        );
+    }
+
+    #[test]
+    fn army_rules_allow_at_most_one_flag() {
+        let mut rules = Rules::default();
+        rules.army.max[Piece::Flag.army_slot().unwrap()] = 2;
+        assert_eq!(rules.validate(), Err(RuleError::InvalidArmyRules));
+    }
+
+    #[test]
+    fn validate_rejects_each_broken_setting() {
+        let base = Rules::default();
+        assert_eq!(base.validate(), Ok(()));
+
+        let narrow = Rules { width: 1, ..base };
+        assert_eq!(narrow.validate(), Err(RuleError::InvalidBoardSize));
+        let tall = Rules {
+            height: MAX_HEIGHT + 1,
+            ..base
+        };
+        assert_eq!(tall.validate(), Err(RuleError::InvalidBoardSize));
+
+        let endless = Rules {
+            tie_break: TieBreakMode::Repeat,
+            max_tie_rounds: 0,
+            ..base
+        };
+        assert_eq!(endless.validate(), Err(RuleError::InvalidTieRounds));
+
+        let mut limited = Rules {
+            limited_weapons: true,
+            weapon_stock: [2, 2, 2, 0, 0],
+            ..base
+        };
+        assert_eq!(limited.validate(), Ok(()));
+        // lizards are not part of the classic set
+        limited.weapon_stock = [2, 2, 2, 1, 0];
+        assert_eq!(limited.validate(), Err(RuleError::InvalidWeaponStock));
+        limited.weapon_stock = [0; WEAPON_KINDS];
+        assert_eq!(limited.validate(), Err(RuleError::InvalidWeaponStock));
+
+        let mut walled = Rules {
+            terrain: Terrain::Custom,
+            ..base
+        };
+        let middle = walled.cell_at(3, 2).unwrap() as usize;
+        walled.blocked[middle / 8] |= 1 << (middle % 8);
+        assert_eq!(walled.validate(), Ok(()));
+        let spawn = walled.cell_at(0, 0).unwrap() as usize;
+        walled.blocked[spawn / 8] |= 1 << (spawn % 8);
+        assert_eq!(walled.validate(), Err(RuleError::InvalidTerrain));
+        let open_with_walls = Rules {
+            terrain: Terrain::Open,
+            ..walled
+        };
+        assert_eq!(open_with_walls.validate(), Err(RuleError::InvalidTerrain));
+        let small_lakes = Rules {
+            width: 4,
+            terrain: Terrain::Lakes,
+            ..base
+        };
+        assert_eq!(small_lakes.validate(), Err(RuleError::InvalidTerrain));
+
+        let scout_lizard = Rules {
+            scout_weapon: Weapon::Lizard,
+            ..base
+        };
+        assert_eq!(scout_lizard.validate(), Err(RuleError::InvalidArmyRules));
+        let mut oversized = base;
+        oversized.army.size = 15;
+        assert_eq!(oversized.validate(), Err(RuleError::InvalidArmyRules));
+    }
+}
@@ -0,0 +1,176 @@
+/// The weapons behind both pieces and tie-break choices. Both resolve
+/// through `duel`, so a battle and a tie-break can never disagree.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Weapon {
+    Rock = 0,
+    Paper = 1,
+    Scissors = 2,
+    Lizard = 3,
+    Spock = 4,
+}
+
+impl From<u8> for Weapon {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Paper,
+            2 => Self::Scissors,
+            3 => Self::Lizard,
+            4 => Self::Spock,
+            _ => Self::Rock,
+        }
+    }
+}
+
+pub const WEAPON_KINDS: usize = 5;
+pub const WEAPONS: [Weapon; WEAPON_KINDS] = [
+    Weapon::Rock,
+    Weapon::Paper,
+    Weapon::Scissors,
+    Weapon::Lizard,
+    Weapon::Spock,
+];
+
+/// `BEATS[w]` holds the two weapons `w` defeats.
+const BEATS: [[Weapon; 2]; WEAPON_KINDS] = {
+    use Weapon::*;
+    [
+        [Scissors, Lizard], // Rock
+        [Rock, Spock],      // Paper
+        [Paper, Lizard],    // Scissors
+        [Spock, Paper],     // Lizard
+        [Scissors, Rock],   // Spock
+    ]
+};
+
+/// 1 if `a` wins, -1 if `b` wins, 0 on equal weapons.
+pub fn duel(a: Weapon, b: Weapon) -> i8 {
+    if a == b {
+        0
+    } else if BEATS[a as usize].contains(&b) {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Which weapons a game is played with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum WeaponSet {
+    Classic = 0,
+    /// Rock, Paper, Scissors, Lizard, Spock.
+    Extended = 1,
+}
+
+impl From<u8> for WeaponSet {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Extended,
+            _ => Self::Classic,
+        }
+    }
+}
+
+impl WeaponSet {
+    pub fn allows(self, w: Weapon) -> bool {
+        self == WeaponSet::Extended || !matches!(w, Weapon::Lizard | Weapon::Spock)
+    }
+}
+
+/// A weapon picked in a tie-break, `None` until it is revealed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Choice {
+    None = 0,
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+    Lizard = 4,
+    Spock = 5,
+}
+
+impl From<u8> for Choice {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Rock,
+            2 => Self::Paper,
+            3 => Self::Scissors,
+            4 => Self::Lizard,
+            5 => Self::Spock,
+            _ => Self::None,
+        }
+    }
+}
+
+impl Choice {
+    pub fn weapon(self) -> Option<Weapon> {
+        match self {
+            Self::None => None,
+            Self::Rock => Some(Weapon::Rock),
+            Self::Paper => Some(Weapon::Paper),
+            Self::Scissors => Some(Weapon::Scissors),
+            Self::Lizard => Some(Weapon::Lizard),
+            Self::Spock => Some(Weapon::Spock),
+        }
+    }
+}
+
+pub fn rps_choice(a: Choice, b: Choice) -> i8 {
+    if a == b {
+        return 0;
+    }
+    match (a.weapon(), b.weapon()) {
+        (Some(a), Some(b)) => duel(a, b),
+        _ => -1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_weapon_beats_two_and_loses_to_two() {
+        for a in WEAPONS {
+            let wins = WEAPONS.iter().filter(|&&b| duel(a, b) == 1).count();
+            let losses = WEAPONS.iter().filter(|&&b| duel(a, b) == -1).count();
+            assert_eq!((wins, losses), (2, 2), "{a:?}");
+            for b in WEAPONS {
+                assert_eq!(duel(a, b), -duel(b, a), "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn lizard_and_spock_follow_the_extended_rules() {
+        use Weapon::*;
+        // scissors cuts paper, paper covers rock, rock crushes lizard,
+        // lizard poisons spock, spock smashes scissors, scissors decapitates
+        // lizard, lizard eats paper, paper disproves spock, spock vaporizes
+        // rock, rock crushes scissors
+        let wins = [
+            (Scissors, Paper),
+            (Paper, Rock),
+            (Rock, Lizard),
+            (Lizard, Spock),
+            (Spock, Scissors),
+            (Scissors, Lizard),
+            (Lizard, Paper),
+            (Paper, Spock),
+            (Spock, Rock),
+            (Rock, Scissors),
+        ];
+        for (a, b) in wins {
+            assert_eq!(duel(a, b), 1, "{a:?} vs {b:?}");
+        }
+    }
+
+    #[test]
+    fn classic_set_leaves_out_lizard_and_spock() {
+        assert!(WeaponSet::Classic.allows(Weapon::Scissors));
+        assert!(!WeaponSet::Classic.allows(Weapon::Lizard));
+        assert!(!WeaponSet::Classic.allows(Weapon::Spock));
+        assert!(WEAPONS.iter().all(|&w| WeaponSet::Extended.allows(w)));
+    }
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use icq_rps_engine::{Piece, RuleError, Side};
 
 #[error_code]
 pub enum ErrorCode {
@@ -34,8 +35,6 @@ pub enum ErrorCode {
     LineupLengthMismatch,
     #[msg("Lineup positions empty")]
     LineupPositionsEmpty,
-    #[msg("Only R/P/S/F/T allowed")]
-    OnlyRpsftAllowed,
     #[msg("Game not active")]
     GameNotActive,
     #[msg("Tie in progress")]
@@ -52,10 +51,231 @@ pub enum ErrorCode {
     AlreadyChose,
     #[msg("Overflow")]
     Overflow,
-    #[msg("You must include exactly one Flag in your lineup")]
-    MustHaveExactlyOneFlag,
-    #[msg("You may place at most one Trap")]
-    TooManyTraps,
-    #[msg("Trap must be placed only on the inner spawn row")]
-    TrapBadRow,
+    #[msg("Battle waiting for reveals")]
+    BattleInProgress,
+    #[msg("No battle pending")]
+    NoBattlePending,
+    #[msg("Piece is not part of the pending battle")]
+    NotInBattle,
+    #[msg("Piece already revealed")]
+    PieceAlreadyRevealed,
+    #[msg("Reveal does not match lineup commitment")]
+    InvalidRevealProof,
+    #[msg("Commitment already used by the opponent")]
+    DuplicateCommitment,
+    #[msg("Both weapon commitments are required first")]
+    WaitingForCommitments,
+    #[msg("Weapon not allowed by this game's weapon set")]
+    InvalidChoice,
+    #[msg("Reveal does not match weapon commitment")]
+    InvalidWeaponReveal,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Reveal window still open")]
+    RevealWindowOpen,
+    #[msg("Timeouts must be non-zero")]
+    InvalidTimeout,
+    #[msg("Timeout not reached yet")]
+    TimeoutNotReached,
+    #[msg("Wager is below the escrow rent-exempt minimum")]
+    WagerTooSmall,
+    #[msg("Pot must be claimed first")]
+    PotUnclaimed,
+    #[msg("Token wager accounts missing")]
+    MissingTokenAccounts,
+    #[msg("Wrong wager mint")]
+    WrongMint,
+    #[msg("Wrong wager vault")]
+    WrongVault,
+    #[msg("Fee above the maximum")]
+    FeeTooHigh,
+    #[msg("Only the config admin may do this")]
+    NotAdmin,
+    #[msg("Wrong treasury")]
+    WrongTreasury,
+    #[msg("A draw offer is already open")]
+    DrawAlreadyOffered,
+    #[msg("No draw offer from the opponent")]
+    NoDrawOffer,
+    #[msg("Board size out of range")]
+    InvalidBoardSize,
+    #[msg("Spawn or trap rows out of range")]
+    InvalidSpawnDepth,
+    #[msg("Flag may not move in this game")]
+    FlagCannotMove,
+    #[msg("Army rules are inconsistent")]
+    InvalidArmyRules,
+    #[msg("Lineup size does not match the army size")]
+    ArmySizeMismatch,
+    #[msg("Lineup has too few of a piece type")]
+    TooFewPieces,
+    #[msg("Lineup has too many of a piece type")]
+    TooManyPieces,
+    #[msg("Lineup costs more than the army budget")]
+    OverBudget,
+    #[msg("Piece is not left in the draft pool")]
+    PieceNotInPool,
+    #[msg("Pick would leave an army impossible to complete")]
+    DraftPickBlocked,
+    #[msg("Lineup does not match the drafted army")]
+    ArmyNotDrafted,
+    #[msg("Blocked terrain layout is invalid")]
+    InvalidTerrain,
+    #[msg("Cell is blocked terrain")]
+    CellBlocked,
+    #[msg("Repeated tie-breaks need at least one round")]
+    InvalidTieRounds,
+    #[msg("No uses of this weapon left")]
+    WeaponExhausted,
+    #[msg("Weapon stock is empty or holds weapons not in play")]
+    InvalidWeaponStock,
+    #[msg("Lineup has too few Rocks")]
+    TooFewRock,
+    #[msg("Lineup has too few Papers")]
+    TooFewPaper,
+    #[msg("Lineup has too few Scissors")]
+    TooFewScissors,
+    #[msg("Lineup has too few Flags")]
+    TooFewFlag,
+    #[msg("Lineup has too few Traps")]
+    TooFewTrap,
+    #[msg("Lineup has too few Lizards")]
+    TooFewLizard,
+    #[msg("Lineup has too few Spocks")]
+    TooFewSpock,
+    #[msg("Lineup has too few Scouts")]
+    TooFewScout,
+    #[msg("Lineup has too few Bombs")]
+    TooFewBomb,
+    #[msg("Lineup has too many Rocks")]
+    TooManyRock,
+    #[msg("Lineup has too many Papers")]
+    TooManyPaper,
+    #[msg("Lineup has too many Scissors")]
+    TooManyScissors,
+    #[msg("Lineup has too many Flags")]
+    TooManyFlag,
+    #[msg("Lineup has too many Traps")]
+    TooManyTrap,
+    #[msg("Lineup has too many Lizards")]
+    TooManyLizard,
+    #[msg("Lineup has too many Spocks")]
+    TooManySpock,
+    #[msg("Lineup has too many Scouts")]
+    TooManyScout,
+    #[msg("Lineup has too many Bombs")]
+    TooManyBomb,
+}
+
+/// Rules engine refusals, with the details the codes alone would lose
+/// written to the log.
+impl From<RuleError> for ErrorCode {
+    fn from(e: RuleError) -> Self {
+        match e {
+            RuleError::InvalidBoardSize => Self::InvalidBoardSize,
+            RuleError::InvalidSpawnDepth => Self::InvalidSpawnDepth,
+            RuleError::InvalidTieRounds => Self::InvalidTieRounds,
+            RuleError::InvalidWeaponStock => Self::InvalidWeaponStock,
+            RuleError::InvalidTerrain => Self::InvalidTerrain,
+            RuleError::InvalidArmyRules => Self::InvalidArmyRules,
+            RuleError::LineupPositionsEmpty => Self::LineupPositionsEmpty,
+            RuleError::ArmySizeMismatch => Self::ArmySizeMismatch,
+            RuleError::TooFewPieces { piece, count, min } => {
+                msg!("too few {:?}: {} < {}", piece, count, min);
+                Self::too_few(piece)
+            }
+            RuleError::TooManyPieces { piece, count, max } => {
+                msg!("too many {:?}: {} > {}", piece, count, max);
+                Self::too_many(piece)
+            }
+            RuleError::OverBudget { cost, budget } => {
+                msg!("army costs {} of {} points", cost, budget);
+                Self::OverBudget
+            }
+            RuleError::ArmyNotDrafted => Self::ArmyNotDrafted,
+            RuleError::PieceNotInPool => Self::PieceNotInPool,
+            RuleError::DraftPickBlocked => Self::DraftPickBlocked,
+            RuleError::BadRow(Side::P0) => Self::Player0BadRow,
+            RuleError::BadRow(Side::P1) => Self::Player1BadRow,
+            RuleError::BadCell => Self::BadCell,
+            RuleError::CellBlocked => Self::CellBlocked,
+            RuleError::CellTaken => Self::CellTaken,
+            RuleError::NotYourTurn => Self::NotYourTurn,
+            RuleError::InvalidMove => Self::InvalidMove,
+            RuleError::FlagCannotMove => Self::FlagCannotMove,
+            RuleError::CannotStackOwnPiece => Self::CannotStackOwnPiece,
+            RuleError::BattleInProgress => Self::BattleInProgress,
+            RuleError::NoBattlePending => Self::NoBattlePending,
+            RuleError::NotInBattle => Self::NotInBattle,
+            RuleError::PieceAlreadyRevealed => Self::PieceAlreadyRevealed,
+            RuleError::TieInProgress => Self::TieInProgress,
+            RuleError::NoTiePending => Self::NoTiePending,
+            RuleError::InvalidChoice => Self::InvalidChoice,
+            RuleError::AlreadyChose => Self::AlreadyChose,
+            RuleError::WeaponExhausted => Self::WeaponExhausted,
+        }
+    }
+}
+
+impl ErrorCode {
+    /// The army kind a lineup has too few of; the generic code only covers
+    /// values that are not army pieces.
+    fn too_few(piece: Piece) -> Self {
+        match piece {
+            Piece::Rock => Self::TooFewRock,
+            Piece::Paper => Self::TooFewPaper,
+            Piece::Scissors => Self::TooFewScissors,
+            Piece::Flag => Self::TooFewFlag,
+            Piece::Trap => Self::TooFewTrap,
+            Piece::Lizard => Self::TooFewLizard,
+            Piece::Spock => Self::TooFewSpock,
+            Piece::Scout => Self::TooFewScout,
+            Piece::Bomb => Self::TooFewBomb,
+            Piece::Empty | Piece::Unknown => Self::TooFewPieces,
+        }
+    }
+
+    fn too_many(piece: Piece) -> Self {
+        match piece {
+            Piece::Rock => Self::TooManyRock,
+            Piece::Paper => Self::TooManyPaper,
+            Piece::Scissors => Self::TooManyScissors,
+            Piece::Flag => Self::TooManyFlag,
+            Piece::Trap => Self::TooManyTrap,
+            Piece::Lizard => Self::TooManyLizard,
+            Piece::Spock => Self::TooManySpock,
+            Piece::Scout => Self::TooManyScout,
+            Piece::Bomb => Self::TooManyBomb,
+            Piece::Empty | Piece::Unknown => Self::TooManyPieces,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icq_rps_engine::ARMY_PIECES;
+
+    #[test]
+    fn every_army_kind_has_its_own_codes() {
+        let mut seen = Vec::new();
+        for piece in ARMY_PIECES {
+            let few = ErrorCode::from(RuleError::TooFewPieces {
+                piece,
+                count: 0,
+                min: 1,
+            });
+            let many = ErrorCode::from(RuleError::TooManyPieces {
+                piece,
+                count: 2,
+                max: 1,
+            });
+            assert!(few.name().ends_with(&format!("{piece:?}")));
+            assert!(many.name().ends_with(&format!("{piece:?}")));
+            seen.extend([u32::from(few), u32::from(many)]);
+        }
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 2 * ARMY_PIECES.len());
+    }
 }
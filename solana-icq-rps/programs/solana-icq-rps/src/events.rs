@@ -1,4 +1,4 @@
-use crate::{Choice, Piece};
+use crate::{Choice, Piece, WEAPON_KINDS};
 use anchor_lang::prelude::*;
 
 #[event]
@@ -6,6 +6,16 @@ pub struct GameCreated {
     pub creator: Pubkey,
 }
 
+#[event]
+pub struct GameCancelled {
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct GameClosed {
+    pub closer: Pubkey,
+}
+
 #[event]
 pub struct GameJoined {
     pub participant: Pubkey,
@@ -44,20 +54,81 @@ pub struct Battle {
     pub outcome: i8,
 }
 #[event]
+pub struct BattleStarted {
+    pub from_idx: u8,
+    pub to_idx: u8,
+}
+#[event]
+pub struct BombExploded {
+    pub from_idx: u8,
+    pub to_idx: u8,
+    pub attacker: Piece,
+}
+
+#[event]
+pub struct PieceRevealed {
+    pub player: Pubkey,
+    pub idx: u8,
+    pub piece: Piece,
+}
+#[event]
 pub struct TieStarted {
     pub from_idx: u8,
     pub to_idx: u8,
 }
 #[event]
 pub struct GameOver {
-    pub winner: Pubkey,
+    pub winner: Option<Pubkey>,
     pub reason: String,
+    pub fee: u64,
+}
+
+#[event]
+pub struct WeaponCommitted {
+    pub player: Pubkey,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct PotClaimed {
+    pub winner: Option<Pubkey>,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DrawOffered {
+    pub player: Pubkey,
+}
+
+#[event]
+pub struct DrawDeclined {
+    pub player: Pubkey,
+}
+
+#[event]
+pub struct PiecePicked {
+    pub player: Pubkey,
+    pub piece: Piece,
+    pub left_in_pool: u16,
 }
 
 #[event]
 pub struct TieChoice {
     pub player: Pubkey,
     pub choice: Choice,
+    pub stock_left: [u8; WEAPON_KINDS],
+}
+#[event]
+pub struct TieRound {
+    pub round: u8,
+    pub outcome: i8,
+    pub p0_choice: Choice,
+    pub p1_choice: Choice,
 }
 #[event]
 pub struct TieResolved {
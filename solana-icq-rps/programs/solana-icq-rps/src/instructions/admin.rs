@@ -0,0 +1,58 @@
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+use crate::program::SolanaIcqRps;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Only the program's upgrade authority may create the config, so nobody can
+/// front-run the deployment and install themselves as admin.
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        seeds = [b"config"],
+        bump,
+        payer = admin,
+        space = Config::SIZE,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, SolanaIcqRps>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_config(ctx: Context<InitConfig>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, ErrorCode::FeeTooHigh);
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.treasury = treasury;
+    config.fee_bps = fee_bps;
+    config.bump = ctx.bumps.config;
+
+    emit!(ConfigUpdated { fee_bps, treasury });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin @ ErrorCode::NotAdmin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+/// Only affects games created afterwards; running games keep their fee.
+pub fn update_config(ctx: Context<UpdateConfig>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, ErrorCode::FeeTooHigh);
+
+    let config = &mut ctx.accounts.config;
+    config.fee_bps = fee_bps;
+    config.treasury = treasury;
+
+    emit!(ConfigUpdated { fee_bps, treasury });
+    Ok(())
+}
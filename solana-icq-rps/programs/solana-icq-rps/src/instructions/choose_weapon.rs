@@ -1,8 +1,9 @@
-use crate::end_turn_or_win;
+use crate::conclude;
 use crate::errors::ErrorCode;
-use crate::events::{Battle, GameOver, MoveMade, TieChoice, TieResolved};
+use crate::events::{TieChoice, TieResolved, TieRound, WeaponCommitted};
 use crate::state::*;
 use anchor_lang::prelude::*;
+use icq_rps_engine::{self as engine, Side, TieEnd, WeaponOutcome};
 
 #[derive(Accounts)]
 pub struct ChooseWeapon<'info> {
@@ -11,126 +12,149 @@ pub struct ChooseWeapon<'info> {
     pub signer: Signer<'info>,
 }
 
-pub fn choose_weapon(ctx: Context<ChooseWeapon>, choice: u8) -> Result<()> {
+pub fn commit_weapon(ctx: Context<ChooseWeapon>, commitment: [u8; 32]) -> Result<()> {
     let g = &mut ctx.accounts.game;
     let me = ctx.accounts.signer.key();
 
     require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
     require!(g.tie_pending, ErrorCode::NoTiePending);
+    // with limited weapons, a spent stock leaves nothing to commit to
+    if let Some(side) = g.side_of(&me) {
+        g.board().check_commit(side).map_err(ErrorCode::from)?;
+    }
 
+    // A copied commitment could be revealed with the opponent's own salt.
     if me == g.player0 {
-        require!(!g.choice_made0, ErrorCode::AlreadyChose);
-        g.choice0 = Choice::from(choice) as u8;
-        g.choice_made0 = true;
+        require!(!g.commit_made0, ErrorCode::AlreadyChose);
+        require!(
+            !g.commit_made1 || g.weapon_commit1 != commitment,
+            ErrorCode::DuplicateCommitment
+        );
+        g.weapon_commit0 = commitment;
+        g.commit_made0 = true;
     } else if me == g.player1 {
-        require!(!g.choice_made1, ErrorCode::AlreadyChose);
-        g.choice1 = Choice::from(choice) as u8;
-        g.choice_made1 = true;
+        require!(!g.commit_made1, ErrorCode::AlreadyChose);
+        require!(
+            !g.commit_made0 || g.weapon_commit0 != commitment,
+            ErrorCode::DuplicateCommitment
+        );
+        g.weapon_commit1 = commitment;
+        g.commit_made1 = true;
     } else {
         return err!(ErrorCode::NotParticipant);
     }
-    emit!(TieChoice {
-        player: me,
-        choice: Choice::from(choice)
-    });
+    emit!(WeaponCommitted { player: me });
 
-    if !(g.choice_made0 && g.choice_made1) {
-        return Ok(());
-    }
+    touch(g)
+}
 
-    let t_from = g.tie_from as usize;
-    let t_to = g.tie_to as usize;
+pub fn reveal_weapon(ctx: Context<ChooseWeapon>, choice: u8, salt: [u8; 32]) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
 
-    let attacker_owner = if g.is_player1_turn {
-        BoardCellOwner::P1
-    } else {
-        BoardCellOwner::P0
+    require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
+    require!(g.tie_pending, ErrorCode::NoTiePending);
+    require!(
+        g.commit_made0 && g.commit_made1,
+        ErrorCode::WaitingForCommitments
+    );
+
+    let Some(side) = g.side_of(&me) else {
+        return err!(ErrorCode::NotParticipant);
     };
-    let defender_owner = if attacker_owner == BoardCellOwner::P0 {
-        BoardCellOwner::P1
-    } else {
-        BoardCellOwner::P0
+    let commitment = match side {
+        Side::P0 => g.weapon_commit0,
+        Side::P1 => g.weapon_commit1,
     };
-
-    let attacker_piece = Piece::from(g.board_pieces[t_from]);
-
-    let p0_choice = Choice::from(g.choice0);
-    let p1_choice = Choice::from(g.choice1);
-    let outcome = rps_choice(p0_choice, p1_choice);
-
-    emit!(TieResolved {
-        outcome,
-        p0_choice,
-        p1_choice
+    require!(
+        weapon_commitment(choice, &salt) == commitment,
+        ErrorCode::InvalidWeaponReveal
+    );
+
+    let mut board = g.board();
+    let outcome = board
+        .reveal_weapon(side, engine::Choice::from(choice))
+        .map_err(ErrorCode::from)?;
+    g.store(&board);
+    emit!(TieChoice {
+        player: me,
+        choice: Choice::from(choice),
+        stock_left: board.weapon_stock[side.idx()],
     });
-
-    let attacker_is_p1 = g.is_player1_turn;
-
-    let attacker_wins = if attacker_is_p1 {
-        outcome == -1
-    } else {
-        outcome == 1
-    };
-    let is_tie = outcome == 0;
-
-    if attacker_wins {
-        if defender_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
+    touch(g)?;
+
+    match outcome {
+        WeaponOutcome::Waiting => Ok(()),
+        WeaponOutcome::Replay { round, choices } => {
+            emit!(TieRound {
+                round,
+                outcome: 0,
+                p0_choice: Choice::from(choices[0]),
+                p1_choice: Choice::from(choices[1]),
+            });
+            clear_weapon_commits(g);
+            Ok(())
         }
-
-        g.board_cells_owner[t_to] = BoardCellOwner::None as u8;
-        g.board_pieces[t_to] = Piece::Empty as u8;
-
-        g.board_cells_owner[t_from] = BoardCellOwner::None as u8;
-        g.board_pieces[t_from] = Piece::Empty as u8;
-
-        g.board_cells_owner[t_to] = attacker_owner as u8;
-        g.board_pieces[t_to] = attacker_piece as u8;
-
-        if attacker_piece == Piece::Flag {
-            if attacker_owner == BoardCellOwner::P0 {
-                g.flag_pos0 = g.tie_to;
-            } else {
-                g.flag_pos1 = g.tie_to;
-            }
+        WeaponOutcome::Resolved { round, tie } => {
+            emit!(TieRound {
+                round,
+                outcome: tie.outcome,
+                p0_choice: Choice::from(tie.choices[0]),
+                p1_choice: Choice::from(tie.choices[1]),
+            });
+            settle_tie(g, tie)
         }
-    } else if is_tie {
-        g.board_cells_owner[t_from] = BoardCellOwner::None as u8;
-        g.board_pieces[t_from] = Piece::Empty as u8;
+    }
+}
 
-        g.board_cells_owner[t_to] = BoardCellOwner::None as u8;
-        g.board_pieces[t_to] = Piece::Empty as u8;
+fn clear_weapon_commits(g: &mut Game) {
+    g.commit_made0 = false;
+    g.commit_made1 = false;
+    g.weapon_commit0 = [0; 32];
+    g.weapon_commit1 = [0; 32];
+}
 
-        if attacker_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
-        }
-        if defender_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
-        }
-    } else {
-        // програв атакер — прибрати його фігуру
-        g.board_cells_owner[t_from] = BoardCellOwner::None as u8;
-        g.board_pieces[t_from] = Piece::Empty as u8;
-
-        if attacker_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
-        }
-    }
+/// A player who revealed may take the tie-break once the opponent has let
+/// the reveal window pass without revealing.
+pub fn claim_tie_forfeit(ctx: Context<ChooseWeapon>) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
+    require!(g.tie_pending, ErrorCode::NoTiePending);
+    require!(
+        g.commit_made0 && g.commit_made1,
+        ErrorCode::WaitingForCommitments
+    );
 
-    g.tie_pending = false;
-    g.choice_made0 = false;
-    g.choice_made1 = false;
-    g.choice0 = Choice::None as u8;
-    g.choice1 = Choice::None as u8;
+    let Some(side) = g.side_of(&me) else {
+        return err!(ErrorCode::NotParticipant);
+    };
+    let (mine, theirs) = match side {
+        Side::P0 => (g.choice_made0, g.choice_made1),
+        Side::P1 => (g.choice_made1, g.choice_made0),
+    };
+    require!(mine && !theirs, ErrorCode::NothingToClaim);
+
+    require!(
+        deadline_passed(g, g.config.turn_timeout_secs)?,
+        ErrorCode::RevealWindowOpen
+    );
+
+    touch(g)?;
+    let mut board = g.board();
+    let tie = board.forfeit_tie(side).map_err(ErrorCode::from)?;
+    g.store(&board);
+    settle_tie(g, tie)
+}
 
-    let pass_to_opponent = !g.is_player1_turn;
-    end_turn_or_win(g, pass_to_opponent)
+/// Announces a settled tie-break and ends the game if it decided it.
+fn settle_tie(g: &mut Game, tie: TieEnd) -> Result<()> {
+    emit!(TieResolved {
+        outcome: tie.outcome,
+        p0_choice: Choice::from(tie.choices[0]),
+        p1_choice: Choice::from(tie.choices[1]),
+    });
+    clear_weapon_commits(g);
+    conclude(g, tie.game_over)
 }
@@ -0,0 +1,235 @@
+use crate::errors::ErrorCode;
+use crate::events::PotClaimed;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+#[derive(Accounts)]
+pub struct ClaimPot<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(mut, seeds = [b"escrow", game.key().as_ref()], bump = game.escrow_bump)]
+    pub escrow: SystemAccount<'info>,
+    #[account(mut, address = game.player0 @ ErrorCode::NotParticipant)]
+    pub player0: SystemAccount<'info>,
+    #[account(mut, address = game.player1 @ ErrorCode::NotParticipant)]
+    pub player1: SystemAccount<'info>,
+    #[account(mut, address = game.treasury @ ErrorCode::WrongTreasury)]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+
+    // only for SPL wagers
+    #[account(address = game.mint @ ErrorCode::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = game.vault @ ErrorCode::WrongVault)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, token::mint = game.mint, token::authority = game.player0)]
+    pub player0_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, token::mint = game.mint, token::authority = game.player1)]
+    pub player1_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, token::mint = game.mint, token::authority = game.treasury)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Pays the pot minus the protocol fee to the winner, or refunds both wagers
+/// when the game ended without one. Funds can only go to the players and the
+/// game's treasury, so anyone may crank it.
+///
+/// Known limitation: a lineup is only held to its declared army one piece
+/// at a time, as pieces are revealed in battle. Pieces still hidden when the
+/// game ends are never opened, so a lineup that lied about them (a bomb
+/// committed where a rock was declared, say) is paid out like an honest
+/// one. Such a piece never fought, but it may have moved the way the
+/// declared kind does; only requiring the winner to reveal every hidden
+/// piece before payout would close this.
+pub fn claim_pot(ctx: Context<ClaimPot>) -> Result<()> {
+    let g = &ctx.accounts.game;
+
+    require!(g.phase() == Phase::Finished, ErrorCode::BadPhase);
+    require!(g.wager > 0 && !g.pot_claimed, ErrorCode::NothingToClaim);
+
+    let pot = if g.mint != Pubkey::default() {
+        claim_tokens(ctx.accounts)?
+    } else {
+        claim_lamports(ctx.accounts)?
+    };
+
+    let g = &mut ctx.accounts.game;
+    g.pot_claimed = true;
+    emit!(PotClaimed {
+        winner: g.winner,
+        amount: pot
+    });
+    Ok(())
+}
+
+/// How the pot splits between player0, player1 and the treasury.
+fn split_pot(g: &Game, pot: u64) -> (u64, u64, u64) {
+    match g.winner {
+        Some(w) => {
+            let fee = protocol_fee(g.fee_bps, pot);
+            if w == g.player0 {
+                (pot - fee, 0, fee)
+            } else {
+                (0, pot - fee, fee)
+            }
+        }
+        None => {
+            let back0 = pot.min(g.wager);
+            (back0, pot - back0, 0)
+        }
+    }
+}
+
+fn claim_lamports(a: &ClaimPot) -> Result<u64> {
+    let g = &a.game;
+    let pot = a.escrow.lamports();
+    let (to0, to1, fee) = split_pot(g, pot);
+    for (to, amount) in [(&a.player0, to0), (&a.player1, to1), (&a.treasury, fee)] {
+        if amount > 0 {
+            pay_from_escrow(
+                &a.escrow,
+                &to.to_account_info(),
+                &a.system_program,
+                &g.key(),
+                g.escrow_bump,
+                amount,
+            )?;
+        }
+    }
+    Ok(pot)
+}
+
+fn claim_tokens(a: &ClaimPot) -> Result<u64> {
+    let g = &a.game;
+    let (Some(mint), Some(vault), Some(p0), Some(p1), Some(treasury), Some(token_program)) = (
+        &a.mint,
+        &a.vault,
+        &a.player0_token,
+        &a.player1_token,
+        &a.treasury_token,
+        &a.token_program,
+    ) else {
+        return err!(ErrorCode::MissingTokenAccounts);
+    };
+
+    let pot = vault.amount;
+    let (to0, to1, fee) = split_pot(g, pot);
+    for (to, amount) in [(p0, to0), (p1, to1), (treasury, fee)] {
+        if amount > 0 {
+            pay_from_vault(
+                vault,
+                to,
+                mint,
+                token_program,
+                &g.key(),
+                g.vault_bump,
+                amount,
+            )?;
+        }
+    }
+    // the creator paid the vault's rent
+    close_vault(
+        vault,
+        &a.player0.to_account_info(),
+        token_program,
+        &g.key(),
+        g.vault_bump,
+    )?;
+    Ok(pot)
+}
+
+pub fn pay_from_escrow<'info>(
+    escrow: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    game: &Pubkey,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"escrow", game.as_ref(), &[bump]];
+    transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Transfer {
+                from: escrow.to_account_info(),
+                to: to.clone(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+pub fn deposit_tokens<'info>(
+    from: &InterfaceAccount<'info, TokenAccount>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &Signer<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: vault.to_account_info(),
+                authority: authority.to_account_info(),
+            },
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+pub fn pay_from_vault<'info>(
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    game: &Pubkey,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"vault", game.as_ref(), &[bump]];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+pub fn close_vault<'info>(
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    game: &Pubkey,
+    bump: u8,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"vault", game.as_ref(), &[bump]];
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault.to_account_info(),
+            destination: destination.clone(),
+            authority: vault.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
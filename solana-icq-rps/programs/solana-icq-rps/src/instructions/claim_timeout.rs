@@ -0,0 +1,64 @@
+use crate::errors::ErrorCode;
+use crate::finish;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimTimeout<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    pub signer: Signer<'info>,
+}
+
+pub fn claim_timeout(ctx: Context<ClaimTimeout>) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    require!(g.player1 != Pubkey::default(), ErrorCode::BadPhase);
+    let is_p0 = me == g.player0;
+    let is_p1 = me == g.player1;
+    require!(is_p0 || is_p1, ErrorCode::NotParticipant);
+
+    let (staller_is_p1, timeout) = match g.phase() {
+        Phase::LineupP0Set => (true, g.config.lineup_timeout_secs),
+        Phase::LineupP1Set => (false, g.config.lineup_timeout_secs),
+        Phase::Active => (active_staller_is_p1(g)?, g.config.turn_timeout_secs),
+        Phase::DraftP0Turn => (false, g.config.lineup_timeout_secs),
+        Phase::DraftP1Turn => (true, g.config.lineup_timeout_secs),
+        Phase::Joined => return err!(ErrorCode::NothingToClaim),
+        _ => return err!(ErrorCode::BadPhase),
+    };
+    require!(staller_is_p1 != is_p1, ErrorCode::NothingToClaim);
+    require!(deadline_passed(g, timeout)?, ErrorCode::TimeoutNotReached);
+
+    // walking away before the game starts costs the wager like any other
+    // stall, so the player kept waiting wins
+    let reason = match g.phase() {
+        Phase::Active => "timeout",
+        Phase::DraftP0Turn | Phase::DraftP1Turn => "draft_timeout",
+        _ => "lineup_timeout",
+    };
+    finish(g, me, reason)
+}
+
+/// Who the game is waiting on while active.
+fn active_staller_is_p1(g: &Game) -> Result<bool> {
+    if g.battle_pending {
+        // the attacker opened the battle, so a hidden attacker is the staller
+        let attacker_hidden = Piece::from(g.board_pieces[g.battle_from as usize]) == Piece::Unknown;
+        return Ok(if attacker_hidden {
+            g.is_player1_turn
+        } else {
+            !g.is_player1_turn
+        });
+    }
+    if g.tie_pending {
+        // a missing reveal is settled by `claim_tie_forfeit` instead
+        return match (g.commit_made0, g.commit_made1) {
+            (true, false) => Ok(true),
+            (false, true) => Ok(false),
+            _ => err!(ErrorCode::NothingToClaim),
+        };
+    }
+    Ok(g.is_player1_turn)
+}
@@ -0,0 +1,114 @@
+use crate::errors::ErrorCode;
+use crate::events::{GameCancelled, GameClosed};
+use crate::state::*;
+use crate::{close_vault, pay_from_escrow, pay_from_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    #[account(
+        mut,
+        close = payer,
+        constraint = game.player0 == payer.key() @ ErrorCode::NotParticipant,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(mut, seeds = [b"escrow", game.key().as_ref()], bump = game.escrow_bump)]
+    pub escrow: SystemAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // only for SPL wagers
+    #[account(address = game.mint @ ErrorCode::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = game.vault @ ErrorCode::WrongVault)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, token::mint = game.mint, token::authority = payer)]
+    pub payer_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// The creator backs out of a game nobody has joined and gets the rent back.
+pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+    let g = &ctx.accounts.game;
+
+    match g.phase() {
+        Phase::Created | Phase::LineupP0Set => {}
+        _ => return err!(ErrorCode::BadPhase),
+    }
+    require!(g.player1 == Pubkey::default(), ErrorCode::BadPhase);
+
+    // only the creator has deposited so far
+    let a = &ctx.accounts;
+    if g.mint != Pubkey::default() {
+        let (Some(mint), Some(vault), Some(to), Some(token_program)) =
+            (&a.mint, &a.vault, &a.payer_token, &a.token_program)
+        else {
+            return err!(ErrorCode::MissingTokenAccounts);
+        };
+        if vault.amount > 0 {
+            pay_from_vault(
+                vault,
+                to,
+                mint,
+                token_program,
+                &g.key(),
+                g.vault_bump,
+                vault.amount,
+            )?;
+        }
+        close_vault(
+            vault,
+            &a.payer.to_account_info(),
+            token_program,
+            &g.key(),
+            g.vault_bump,
+        )?;
+    }
+    let refund = a.escrow.lamports();
+    if refund > 0 {
+        pay_from_escrow(
+            &ctx.accounts.escrow,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program,
+            &g.key(),
+            g.escrow_bump,
+            refund,
+        )?;
+    }
+
+    emit!(GameCancelled {
+        creator: ctx.accounts.payer.key()
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(mut, close = payer)]
+    pub game: Account<'info, Game>,
+    /// Rent always goes back to the creator who paid for the account.
+    #[account(mut, address = game.player0 @ ErrorCode::NotParticipant)]
+    pub payer: SystemAccount<'info>,
+    pub signer: Signer<'info>,
+}
+
+/// Players may close a finished game right away; anyone else has to wait
+/// `CLOSE_GRACE_SECS` so the result stays readable for a while.
+pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+    let g = &ctx.accounts.game;
+    let closer = ctx.accounts.signer.key();
+
+    require!(g.phase() == Phase::Finished, ErrorCode::BadPhase);
+    require!(g.wager == 0 || g.pot_claimed, ErrorCode::PotUnclaimed);
+    if closer != g.player0 && closer != g.player1 {
+        require!(
+            deadline_passed(g, CLOSE_GRACE_SECS)?,
+            ErrorCode::TimeoutNotReached
+        );
+    }
+
+    emit!(GameClosed { closer });
+    Ok(())
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{clear_board, errors::ErrorCode, events::GameCreated, Game, Phase};
+use crate::{
+    clear_board, deposit_tokens, errors::ErrorCode, events::GameCreated, touch, Config, Game,
+    GameConfig, Phase,
+};
 
 #[derive(Accounts)]
 #[instruction(nonce: [u8; 32])]
@@ -14,12 +19,71 @@ pub struct CreateGame<'info> {
     )]
     pub game: Account<'info, Game>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Holds both wagers until the game is settled.
+    #[account(mut, seeds = [b"escrow", game.key().as_ref()], bump)]
+    pub escrow: SystemAccount<'info>,
+
+    /// SPL wagers: the mint to play for. Leave the token accounts out for SOL.
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault", game.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, token::mint = mint, token::authority = payer)]
+    pub payer_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn create_game(ctx: Context<CreateGame>, nonce: [u8; 32]) -> Result<()> {
+pub fn create_game(
+    ctx: Context<CreateGame>,
+    nonce: [u8; 32],
+    wager: u64,
+    config: GameConfig,
+) -> Result<()> {
+    config.validate()?;
+    let a = &ctx.accounts;
+    let (mint, vault) = match (&a.mint, &a.vault, &a.payer_token, &a.token_program) {
+        (Some(mint), Some(vault), Some(from), Some(token_program)) => {
+            require!(wager > 0, ErrorCode::WagerTooSmall);
+            deposit_tokens(from, vault, mint, &a.payer, token_program, wager)?;
+            (mint.key(), vault.key())
+        }
+        (None, None, None, _) => (Pubkey::default(), Pubkey::default()),
+        _ => return err!(ErrorCode::MissingTokenAccounts),
+    };
+
+    // the escrow is a plain system account, so it must stay rent exempt
+    require!(
+        mint != Pubkey::default() || wager == 0 || wager >= Rent::get()?.minimum_balance(0),
+        ErrorCode::WagerTooSmall
+    );
+
+    if mint == Pubkey::default() && wager > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            wager,
+        )?;
+    }
+
     let game = &mut ctx.accounts.game;
     let payer = &ctx.accounts.payer;
 
@@ -29,9 +93,19 @@ pub fn create_game(ctx: Context<CreateGame>, nonce: [u8; 32]) -> Result<()> {
     game.phase = Phase::Created as u8;
     game.is_player1_turn = false;
 
+    game.config = config;
     clear_board(game);
 
     game.nonce = nonce;
+    game.wager = wager;
+    game.escrow_bump = ctx.bumps.escrow;
+    game.mint = mint;
+    game.vault = vault;
+    game.vault_bump = ctx.bumps.vault.unwrap_or_default();
+    game.fee_bps = ctx.accounts.config.fee_bps;
+    game.treasury = ctx.accounts.config.treasury;
+    game.pot_claimed = false;
+    touch(game)?;
 
     emit!(GameCreated {
         creator: payer.key()
@@ -0,0 +1,45 @@
+use crate::errors::ErrorCode;
+use crate::events::PiecePicked;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use icq_rps_engine::{self as engine, Side};
+
+#[derive(Accounts)]
+pub struct DraftPick<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    pub signer: Signer<'info>,
+}
+
+/// Takes one piece from the shared pool. Player1 picks first since player0
+/// gets the first move; the draft ends once both armies are full.
+pub fn draft_pick(ctx: Context<DraftPick>, piece: u8) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    let side = match g.phase() {
+        Phase::DraftP0Turn => Side::P0,
+        Phase::DraftP1Turn => Side::P1,
+        _ => return err!(ErrorCode::BadPhase),
+    };
+    require!(me == g.player(side), ErrorCode::NotYourTurn);
+
+    let mut board = g.board();
+    let picked = board
+        .draft_pick(side, engine::Piece::from(piece))
+        .map_err(ErrorCode::from)?;
+    g.store(&board);
+    touch(g)?;
+    emit!(PiecePicked {
+        player: me,
+        piece: Piece::from(piece),
+        left_in_pool: picked.left_in_pool,
+    });
+
+    g.phase = match (picked.done, side) {
+        (true, _) => Phase::Joined as u8,
+        (false, Side::P0) => Phase::DraftP1Turn as u8,
+        (false, Side::P1) => Phase::DraftP0Turn as u8,
+    };
+    Ok(())
+}
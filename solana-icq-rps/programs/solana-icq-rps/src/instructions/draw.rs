@@ -0,0 +1,73 @@
+use crate::errors::ErrorCode;
+use crate::events::{DrawDeclined, DrawOffered};
+use crate::finish_no_winner;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Draw<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    pub signer: Signer<'info>,
+}
+
+fn side_of(g: &Game, who: Pubkey) -> Result<BoardCellOwner> {
+    if who == g.player0 {
+        Ok(BoardCellOwner::P0)
+    } else if who == g.player1 {
+        Ok(BoardCellOwner::P1)
+    } else {
+        err!(ErrorCode::NotParticipant)
+    }
+}
+
+/// Either player may offer; the offer stands until accepted, declined or the
+/// next move. Offers leave the turn clock alone, so offering and withdrawing
+/// cannot hold off `claim_timeout`.
+pub fn offer_draw(ctx: Context<Draw>) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
+    let side = side_of(g, me)?;
+    require!(
+        BoardCellOwner::from(g.draw_offer) == BoardCellOwner::None,
+        ErrorCode::DrawAlreadyOffered
+    );
+
+    g.draw_offer = side as u8;
+    emit!(DrawOffered { player: me });
+    Ok(())
+}
+
+pub fn accept_draw(ctx: Context<Draw>) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
+    let side = side_of(g, me)?;
+    let offer = BoardCellOwner::from(g.draw_offer);
+    require!(
+        offer != BoardCellOwner::None && offer != side,
+        ErrorCode::NoDrawOffer
+    );
+
+    finish_no_winner(g, "draw_agreed")
+}
+
+/// The opponent turns the offer down, or the offering player withdraws it.
+pub fn decline_draw(ctx: Context<Draw>) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
+    side_of(g, me)?;
+    require!(
+        BoardCellOwner::from(g.draw_offer) != BoardCellOwner::None,
+        ErrorCode::NoDrawOffer
+    );
+
+    g.draw_offer = BoardCellOwner::None as u8;
+    emit!(DrawDeclined { player: me });
+    Ok(())
+}
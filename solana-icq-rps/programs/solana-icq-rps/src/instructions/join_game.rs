@@ -1,16 +1,36 @@
 use crate::events::GameJoined;
-use crate::{errors::ErrorCode, Game, Phase};
+use crate::{deposit_tokens, errors::ErrorCode, touch, Game, Phase};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
     #[account(mut)]
     pub game: Account<'info, Game>,
+    #[account(mut, seeds = [b"escrow", game.key().as_ref()], bump = game.escrow_bump)]
+    pub escrow: SystemAccount<'info>,
+    #[account(mut)]
     pub joiner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // only for SPL wagers: the joiner stakes the game's mint into its vault
+    #[account(address = game.mint @ ErrorCode::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = game.vault @ ErrorCode::WrongVault)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = joiner,
+        token::token_program = token_program,
+    )]
+    pub joiner_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let game = &ctx.accounts.game;
     let joiner = ctx.accounts.joiner.key();
 
     match game.phase() {
@@ -24,10 +44,37 @@ pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
         ErrorCode::NotAllowedJoinGame
     );
 
+    let a = &ctx.accounts;
+    if game.mint != Pubkey::default() {
+        match (&a.mint, &a.vault, &a.joiner_token, &a.token_program) {
+            (Some(mint), Some(vault), Some(from), Some(token_program)) => {
+                deposit_tokens(from, vault, mint, &a.joiner, token_program, game.wager)?
+            }
+            _ => return err!(ErrorCode::MissingTokenAccounts),
+        }
+    } else if game.wager > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.joiner.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            game.wager,
+        )?;
+    }
+
+    let game = &mut ctx.accounts.game;
     game.player1 = joiner;
+    touch(game)?;
 
     if game.phase() == Phase::Created {
-        game.phase = Phase::Joined as u8;
+        game.phase = if game.config.army.draft {
+            Phase::DraftP1Turn as u8
+        } else {
+            Phase::Joined as u8
+        };
     }
 
     emit!(GameJoined {
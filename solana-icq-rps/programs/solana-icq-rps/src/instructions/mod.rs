@@ -13,5 +13,32 @@ pub mod move_piece;
 
 pub use move_piece::*;
 
+pub mod simulate_move;
+pub use simulate_move::*;
+
+pub mod reveal_piece;
+pub use reveal_piece::*;
+
 pub mod choose_weapon;
 pub use choose_weapon::*;
+
+pub mod draft;
+pub use draft::*;
+
+pub mod claim_timeout;
+pub use claim_timeout::*;
+
+pub mod resign;
+pub use resign::*;
+
+pub mod draw;
+pub use draw::*;
+
+pub mod close_game;
+pub use close_game::*;
+
+pub mod claim_pot;
+pub use claim_pot::*;
+
+pub mod admin;
+pub use admin::*;
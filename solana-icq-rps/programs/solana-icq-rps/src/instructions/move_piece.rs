@@ -1,7 +1,8 @@
 use crate::errors::ErrorCode;
-use crate::events::{Battle, GameOver, MoveMade, TieStarted};
+use crate::events::{Battle, BattleStarted, BombExploded, GameOver, MoveMade, TieStarted};
 use crate::state::*;
 use anchor_lang::prelude::*;
+use icq_rps_engine::{self as engine, Board, GameEnd, MoveOutcome};
 
 #[derive(Accounts)]
 pub struct MovePiece<'info> {
@@ -26,12 +27,9 @@ pub fn move_piece_xy(
     to_x: u8,
     to_y: u8,
 ) -> Result<()> {
-    require!(
-        from_x < WIDTH && from_y < HEIGHT && to_x < WIDTH && to_y < HEIGHT,
-        ErrorCode::BadCell
-    );
-    let from_idx = from_y * WIDTH + from_x;
-    let to_idx = to_y * WIDTH + to_x;
+    let g = &ctx.accounts.game;
+    let from_idx = cell_at(g, from_x, from_y)?;
+    let to_idx = cell_at(g, to_x, to_y)?;
     do_move_piece(
         &mut ctx.accounts.game,
         &ctx.accounts.signer,
@@ -45,234 +43,121 @@ pub fn move_piece_xy(
 fn do_move_piece(g: &mut Game, signer: &Signer, from_idx: u8, to_idx: u8) -> Result<()> {
     let me = signer.key();
 
-    require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
-    require!(!g.tie_pending, ErrorCode::TieInProgress);
+    let (board, outcome) = play_move(g, me, from_idx, to_idx)?;
+    g.store(&board);
+    // an offer was made for the position before this move
+    g.draw_offer = BoardCellOwner::None as u8;
+    touch(g)?;
 
-    validate_cell(from_idx)?;
-    validate_cell(to_idx)?;
-    require!(_adjacent_orth(from_idx, to_idx), ErrorCode::InvalidMove);
-
-    let current = if g.is_player1_turn {
-        g.player1
-    } else {
-        g.player0
-    };
-    require!(me == current, ErrorCode::NotYourTurn);
-
-    let me_owner = if g.is_player1_turn {
-        BoardCellOwner::P1
-    } else {
-        BoardCellOwner::P0
-    };
-
-    let from = from_idx as usize;
-    let to = to_idx as usize;
-
-    require!(
-        BoardCellOwner::from(g.board_cells_owner[from]) == me_owner,
-        ErrorCode::InvalidMove
-    );
-
-    let attacker = Piece::from(g.board_pieces[from]);
-    require!(attacker != Piece::Empty, ErrorCode::InvalidMove);
-    require!(attacker != Piece::Trap, ErrorCode::InvalidMove);
-
-    let dest_owner = BoardCellOwner::from(g.board_cells_owner[to]);
-    let defender = Piece::from(g.board_pieces[to]);
-
-    if dest_owner == BoardCellOwner::None {
-        g.board_cells_owner[from] = BoardCellOwner::None as u8;
-        g.board_pieces[from] = Piece::Empty as u8;
-
-        g.board_cells_owner[to] = me_owner as u8;
-        g.board_pieces[to] = attacker as u8;
-
-        if attacker == Piece::Flag {
-            if me_owner == BoardCellOwner::P0 {
-                g.flag_pos0 = to_idx;
-            } else {
-                g.flag_pos1 = to_idx;
-            }
-        }
-
-        emit!(MoveMade {
-            player: me,
-            from_idx,
-            to_idx
-        });
-        return end_turn_or_win(g, !g.is_player1_turn);
-    }
-
-    require!(dest_owner != me_owner, ErrorCode::CannotStackOwnPiece);
-
-    if defender == Piece::Trap {
-        g.board_cells_owner[from] = BoardCellOwner::None as u8;
-        g.board_pieces[from] = Piece::Empty as u8;
-
-        if me_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
-        }
-
-        if attacker == Piece::Flag {
-            let trap_owner_is_p0 = dest_owner == BoardCellOwner::P0;
-            let trap_owner_pubkey = if trap_owner_is_p0 {
-                g.player0
-            } else {
-                g.player1
-            };
-
-            emit!(Battle {
+    match outcome {
+        MoveOutcome::Moved { game_over } => {
+            emit!(MoveMade {
+                player: me,
                 from_idx,
-                to_idx,
-                attacker,
-                defender,
-                outcome: -1,
+                to_idx
             });
-
-            return finish(g, trap_owner_pubkey, "flag_walked_into_trap");
+            conclude(g, game_over)
         }
+        MoveOutcome::Attacked { resolved } => {
+            emit!(BattleStarted { from_idx, to_idx });
+            match resolved {
+                Some(battle) => apply_battle(g, me, battle),
+                None => Ok(()),
+            }
+        }
+    }
+}
 
-        emit!(Battle {
-            from_idx,
-            to_idx,
-            attacker,
-            defender,
-            outcome: -1,
-        });
-
-        emit!(MoveMade {
-            player: me,
-            from_idx,
-            to_idx,
-        });
-
-        return end_turn_or_win(g, !g.is_player1_turn);
+/// Plays `me`'s move on a copy of the board, leaving `g` as it is.
+pub fn play_move(
+    g: &Game,
+    me: Pubkey,
+    from_idx: u8,
+    to_idx: u8,
+) -> std::result::Result<(Board, MoveOutcome), ErrorCode> {
+    if g.phase() != Phase::Active {
+        return Err(ErrorCode::GameNotActive);
     }
+    let side = g.side_of(&me).ok_or(ErrorCode::NotYourTurn)?;
+    let mut board = g.board();
+    let outcome = board.move_piece(side, from_idx, to_idx)?;
+    Ok((board, outcome))
+}
 
-    if defender == Piece::Flag {
-        g.board_cells_owner[from] = BoardCellOwner::None as u8;
-        g.board_pieces[from] = Piece::Empty as u8;
-        if me_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
+/// Emits what a settled battle did; `mover` is the attacking player.
+pub fn apply_battle(g: &mut Game, mover: Pubkey, battle: engine::Battle) -> Result<()> {
+    let r = match battle {
+        engine::Battle::Fought(r) => r,
+        engine::Battle::Tie { from, to } => {
+            emit!(TieStarted {
+                from_idx: from,
+                to_idx: to
+            });
+            return Ok(());
         }
-
-        g.board_cells_owner[to] = BoardCellOwner::None as u8;
-        g.board_pieces[to] = Piece::Empty as u8;
-
-        emit!(Battle {
-            from_idx,
-            to_idx,
-            attacker,
-            defender,
-            outcome: 1
+    };
+    let attacker = Piece::from(r.attacker);
+    if r.bomb {
+        emit!(BombExploded {
+            from_idx: r.from,
+            to_idx: r.to,
+            attacker
         });
-
-        return finish(
-            g,
-            if me_owner == BoardCellOwner::P0 {
-                g.player0
-            } else {
-                g.player1
-            },
-            "captured_flag",
-        );
-    }
-
-    let outcome = rps(attacker, defender);
-
-    if outcome == 0 {
-        g.tie_pending = true;
-        g.tie_from = from_idx;
-        g.tie_to = to_idx;
-        g.choice_made0 = false;
-        g.choice_made1 = false;
-        emit!(TieStarted { from_idx, to_idx });
-        return Ok(());
     }
-
     emit!(Battle {
-        from_idx,
-        to_idx,
+        from_idx: r.from,
+        to_idx: r.to,
         attacker,
-        defender,
-        outcome
+        defender: Piece::from(r.defender),
+        outcome: r.outcome,
     });
-
-    if outcome == 1 {
-        if dest_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
-        }
-
-        g.board_cells_owner[to] = BoardCellOwner::None as u8;
-        g.board_pieces[to] = Piece::Empty as u8;
-
-        g.board_cells_owner[from] = BoardCellOwner::None as u8;
-        g.board_pieces[from] = Piece::Empty as u8;
-
-        g.board_cells_owner[to] = me_owner as u8;
-        g.board_pieces[to] = attacker as u8;
-
-        if attacker == Piece::Flag {
-            if me_owner == BoardCellOwner::P0 {
-                g.flag_pos0 = to_idx;
-            } else {
-                g.flag_pos1 = to_idx;
-            }
-        }
-    } else {
-        g.board_cells_owner[from] = BoardCellOwner::None as u8;
-        g.board_pieces[from] = Piece::Empty as u8;
-        if me_owner == BoardCellOwner::P0 {
-            g.live_player0 = g.live_player0.saturating_sub(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_sub(1);
-        }
+    if r.turn_passed {
+        emit!(MoveMade {
+            player: mover,
+            from_idx: r.from,
+            to_idx: r.to,
+        });
     }
-
-    emit!(MoveMade {
-        player: me,
-        from_idx,
-        to_idx
-    });
-    end_turn_or_win(g, !g.is_player1_turn)
+    conclude(g, r.game_over)
 }
 
-fn _adjacent_orth(from_idx: u8, to_idx: u8) -> bool {
-    let fy = _y(from_idx);
-    let fx = _x(from_idx);
-    let ty = _y(to_idx);
-    let tx = _x(to_idx);
-    let dy = if fy > ty { fy - ty } else { ty - fy };
-    let dx = if fx > tx { fx - tx } else { tx - fx };
-    (dx + dy) == 1
+/// Finishes the game if the rules engine says it is over.
+pub fn conclude(g: &mut Game, end: Option<GameEnd>) -> Result<()> {
+    match end {
+        None => Ok(()),
+        Some(GameEnd {
+            winner: Some(side),
+            reason,
+        }) => finish(g, g.player(side), reason.as_str()),
+        Some(GameEnd {
+            winner: None,
+            reason,
+        }) => finish_no_winner(g, reason.as_str()),
+    }
 }
 
-pub fn end_turn_or_win(g: &mut Game, opponent_turn: bool) -> Result<()> {
-    if g.live_player0 == 0 || g.live_player1 == 0 {
-        let winner = if g.live_player0 == 0 {
-            g.player1
-        } else {
-            g.player0
-        };
-        finish(g, winner, "no_pieces_left")?;
-        return Ok(());
-    }
-    g.is_player1_turn = opponent_turn;
+pub fn finish(g: &mut Game, winner: Pubkey, reason: &str) -> Result<()> {
+    g.phase = Phase::Finished as u8;
+    g.winner = Some(winner);
+    touch(g)?;
+    emit!(GameOver {
+        winner: Some(winner),
+        reason: reason.to_string(),
+        fee: protocol_fee(g.fee_bps, g.wager.saturating_mul(2)),
+    });
     Ok(())
 }
 
-fn finish(g: &mut Game, winner: Pubkey, reason: &str) -> Result<()> {
+/// Ends the game in a draw; wagers go back to both players.
+pub fn finish_no_winner(g: &mut Game, reason: &str) -> Result<()> {
     g.phase = Phase::Finished as u8;
-    g.winner = Some(winner);
+    g.winner = None;
+    g.draw_offer = BoardCellOwner::None as u8;
+    touch(g)?;
     emit!(GameOver {
-        winner,
-        reason: reason.to_string()
+        winner: None,
+        reason: reason.to_string(),
+        fee: 0,
     });
     Ok(())
 }
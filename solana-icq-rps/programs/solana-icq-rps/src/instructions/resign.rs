@@ -0,0 +1,30 @@
+use crate::errors::ErrorCode;
+use crate::finish;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Resign<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    pub signer: Signer<'info>,
+}
+
+pub fn resign(ctx: Context<Resign>) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    require!(g.phase() != Phase::Finished, ErrorCode::BadPhase);
+    // nobody to hand the win to before someone joins
+    require!(g.player1 != Pubkey::default(), ErrorCode::BadPhase);
+
+    let opponent = if me == g.player0 {
+        g.player1
+    } else if me == g.player1 {
+        g.player0
+    } else {
+        return err!(ErrorCode::NotParticipant);
+    };
+
+    finish(g, opponent, "resigned")
+}
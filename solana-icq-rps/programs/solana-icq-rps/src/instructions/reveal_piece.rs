@@ -0,0 +1,59 @@
+use crate::errors::ErrorCode;
+use crate::events::PieceRevealed;
+use crate::state::*;
+use crate::{apply_battle, conclude};
+use anchor_lang::prelude::*;
+use icq_rps_engine::{self as engine, RevealOutcome, Side};
+
+#[derive(Accounts)]
+pub struct RevealPiece<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    pub signer: Signer<'info>,
+}
+
+pub fn reveal_piece(
+    ctx: Context<RevealPiece>,
+    idx: u8,
+    piece: u8,
+    salt: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let g = &mut ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    require!(g.phase() == Phase::Active, ErrorCode::GameNotActive);
+    let Some(side) = g.side_of(&me) else {
+        return err!(ErrorCode::NotParticipant);
+    };
+
+    let mut board = g.board();
+    let origin = board.revealable(side, idx).map_err(ErrorCode::from)?;
+    let root = match side {
+        Side::P0 => g.lineup_commit0,
+        Side::P1 => g.lineup_commit1,
+    };
+    require!(
+        verify_lineup_proof(&root, origin, lineup_leaf(origin, piece, &salt), &proof),
+        ErrorCode::InvalidRevealProof
+    );
+
+    touch(g)?;
+    emit!(PieceRevealed {
+        player: me,
+        idx,
+        piece: Piece::from(piece),
+    });
+
+    let attacker = g.player(board.turn);
+    let outcome = board
+        .reveal(side, idx, engine::Piece::from(piece))
+        .map_err(ErrorCode::from)?;
+    g.store(&board);
+    match outcome {
+        RevealOutcome::Waiting => Ok(()),
+        RevealOutcome::Resolved(battle) => apply_battle(g, attacker, battle),
+        // The proof is valid, so the lineup itself broke the rules: forfeit.
+        RevealOutcome::Illegal(end) => conclude(g, Some(end)),
+    }
+}
@@ -0,0 +1,71 @@
+use crate::play_move;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use icq_rps_engine::{Battle, MoveOutcome};
+
+#[derive(Accounts)]
+pub struct SimulateMove<'info> {
+    pub game: Account<'info, Game>,
+    pub signer: Signer<'info>,
+}
+
+/// What a move would do, as seen before any piece in it is revealed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum MoveResult {
+    /// The move would fail with this `ErrorCode` number.
+    Illegal {
+        error: u32,
+    },
+    Moved,
+    /// A battle starts and waits for the hidden pieces to be revealed.
+    BattleStarted,
+    /// Both pieces are known: 1 if the attacker wins, -1 if it loses, 0 if
+    /// both are removed.
+    Battle {
+        outcome: i8,
+    },
+    /// Equal pieces: a weapon tie-break starts.
+    Tie,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MovePreview {
+    pub result: MoveResult,
+    /// Set when the move ends the game, to the `GameOver` reason.
+    pub game_over: Option<String>,
+    pub winner: Option<Pubkey>,
+}
+
+/// Runs the signer's move against a copy of the game without writing it.
+/// The preview is the instruction's return data, so clients read it with
+/// `simulateTransaction`.
+pub fn simulate_move(ctx: Context<SimulateMove>, from_idx: u8, to_idx: u8) -> Result<MovePreview> {
+    let g = &ctx.accounts.game;
+    let me = ctx.accounts.signer.key();
+
+    let outcome = match play_move(g, me, from_idx, to_idx) {
+        Ok((_, outcome)) => outcome,
+        Err(e) => {
+            return Ok(MovePreview {
+                result: MoveResult::Illegal { error: e.into() },
+                game_over: None,
+                winner: None,
+            })
+        }
+    };
+    let (result, end) = match outcome {
+        MoveOutcome::Moved { game_over } => (MoveResult::Moved, game_over),
+        MoveOutcome::Attacked { resolved: None } => (MoveResult::BattleStarted, None),
+        MoveOutcome::Attacked {
+            resolved: Some(Battle::Tie { .. }),
+        } => (MoveResult::Tie, None),
+        MoveOutcome::Attacked {
+            resolved: Some(Battle::Fought(r)),
+        } => (MoveResult::Battle { outcome: r.outcome }, r.game_over),
+    };
+    Ok(MovePreview {
+        result,
+        game_over: end.map(|e| e.reason.as_str().to_string()),
+        winner: end.and_then(|e| e.winner).map(|side| g.player(side)),
+    })
+}
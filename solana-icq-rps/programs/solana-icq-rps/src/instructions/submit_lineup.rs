@@ -2,6 +2,7 @@ use crate::errors::ErrorCode;
 use crate::events::{GameStarted, LineupSubmitted};
 use crate::state::*;
 use anchor_lang::prelude::*;
+use icq_rps_engine::Side;
 
 #[derive(Accounts)]
 pub struct SubmitLineup<'info> {
@@ -13,13 +14,15 @@ pub struct SubmitLineup<'info> {
 pub fn submit_lineup(
     ctx: Context<SubmitLineup>,
     positions: Vec<u8>,
-    pieces: Vec<u8>,
+    army: [u8; ARMY_KINDS],
+    commitment: [u8; 32],
 ) -> Result<()> {
     do_submit_lineup(
         &mut ctx.accounts.game,
         &ctx.accounts.signer,
         &positions,
-        &pieces,
+        army,
+        commitment,
     )
 }
 
@@ -32,37 +35,38 @@ pub fn submit_lineup_xy(
     ctx: Context<SubmitLineupXy>,
     xs: Vec<u8>,
     ys: Vec<u8>,
-    pieces: Vec<u8>,
+    army: [u8; ARMY_KINDS],
+    commitment: [u8; 32],
 ) -> Result<()> {
-    require!(
-        xs.len() == ys.len() && xs.len() == pieces.len(),
-        ErrorCode::LineupLengthMismatch
-    );
-    let mut pos = Vec::with_capacity(xs.len());
-    for i in 0..xs.len() {
-        require!(xs[i] < WIDTH && ys[i] < HEIGHT, ErrorCode::BadCell);
-        pos.push(ys[i] * WIDTH + xs[i]);
-    }
+    require!(xs.len() == ys.len(), ErrorCode::LineupLengthMismatch);
+    let g = &ctx.accounts.inner.game;
+    let pos = xs
+        .iter()
+        .zip(&ys)
+        .map(|(&x, &y)| cell_at(g, x, y))
+        .collect::<Result<Vec<u8>>>()?;
     do_submit_lineup(
         &mut ctx.accounts.inner.game,
         &ctx.accounts.inner.signer,
         &pos,
-        &pieces,
+        army,
+        commitment,
     )
 }
 
 // -------- core logic --------
 
-fn do_submit_lineup(g: &mut Game, signer: &Signer, positions: &[u8], pieces: &[u8]) -> Result<()> {
+fn do_submit_lineup(
+    g: &mut Game,
+    signer: &Signer,
+    positions: &[u8],
+    army: [u8; ARMY_KINDS],
+    commitment: [u8; 32],
+) -> Result<()> {
     match g.phase() {
         Phase::Created | Phase::Joined | Phase::LineupP0Set | Phase::LineupP1Set => {}
         _ => return err!(ErrorCode::BadPhase),
     }
-    require!(
-        positions.len() == pieces.len(),
-        ErrorCode::LineupLengthMismatch
-    );
-    require!(!positions.is_empty(), ErrorCode::LineupPositionsEmpty);
 
     let s = signer.key();
     let is_p0 = s == g.player0;
@@ -81,95 +85,37 @@ fn do_submit_lineup(g: &mut Game, signer: &Signer, positions: &[u8], pieces: &[u
         );
     }
 
-    let mut flag_count = 0usize;
-    let mut flag_idx: u8 = 0;
-    let mut trap_count: usize = 0;
-    let mut trap_idx: u8 = 0;
-
-    for (i, &idx) in positions.iter().enumerate() {
-        let p = Piece::from(pieces[i]);
-        if p == Piece::Flag {
-            flag_count += 1;
-            flag_idx = idx;
-        }
-        if p == Piece::Trap {
-            trap_count += 1;
-            trap_idx = idx;
-        }
-    }
-
-    require!(flag_count == 1, ErrorCode::MustHaveExactlyOneFlag);
-    require!(trap_count <= 1, ErrorCode::TooManyTraps);
-
-    if trap_count == 1 {
-        let trap_y = _y(trap_idx);
-
-        if is_p0 {
-            require!(trap_y == 4 || trap_y == 5, ErrorCode::TrapBadRow);
-        } else {
-            require!(trap_y == 0 || trap_y == 1, ErrorCode::TrapBadRow);
-        }
-    }
-
-    for (i, &idx) in positions.iter().enumerate() {
-        validate_cell(idx)?;
-        let cell = idx as usize;
-        require!(
-            g.board_cells_owner[cell] == BoardCellOwner::None as u8
-                && g.board_pieces[cell] == Piece::Empty as u8,
-            ErrorCode::CellTaken
-        );
-        if is_p0 {
-            require!(is_p0_spawn(idx), ErrorCode::Player0BadRow);
-        } else {
-            require!(is_p1_spawn(idx), ErrorCode::Player1BadRow);
-        }
-
-        let p = Piece::from(pieces[i]);
+    // The lineup clock only runs once the opponent is in and already placed.
+    let opponent_placed = if is_p0 {
+        g.phase() == Phase::LineupP1Set
+    } else {
+        g.phase() == Phase::LineupP0Set
+    };
+    if opponent_placed && g.player1 != Pubkey::default() {
         require!(
-            matches!(
-                p,
-                Piece::Rock | Piece::Paper | Piece::Scissors | Piece::Flag | Piece::Trap
-            ),
-            ErrorCode::OnlyRpsftAllowed
+            !deadline_passed(g, g.config.lineup_timeout_secs)?,
+            ErrorCode::PlaceFlagDeadlinePassed
         );
-
-        g.board_cells_owner[cell] = if is_p0 {
-            BoardCellOwner::P0 as u8
-        } else {
-            BoardCellOwner::P1 as u8
-        };
-        g.board_pieces[cell] = p as u8;
-        if is_p0 {
-            g.live_player0 = g.live_player0.saturating_add(1);
-        } else {
-            g.live_player1 = g.live_player1.saturating_add(1);
-        }
-
-        if p == Piece::Flag {
-            if is_p0 {
-                g.flag_pos0 = idx;
-            } else {
-                g.flag_pos1 = idx;
-            }
-        }
     }
 
+    // Only occupied cells go on chain; piece types stay behind the commitment
+    // until they are revealed in a battle.
+    let side = if is_p0 { Side::P0 } else { Side::P1 };
+    let mut board = g.board();
+    board
+        .place_lineup(side, positions, &army)
+        .map_err(ErrorCode::from)?;
+    g.store(&board);
+
     if is_p0 {
-        require!(
-            g.phase() != Phase::LineupP0Set,
-            ErrorCode::Player0LineupAlreadyPlaced
-        );
+        g.lineup_commit0 = commitment;
         g.phase = if g.phase() == Phase::LineupP1Set {
             Phase::Active as u8
         } else {
             Phase::LineupP0Set as u8
         };
     } else {
-        require!(
-            g.phase() != Phase::LineupP1Set,
-            ErrorCode::Player1LineupAlreadyPlaced
-        );
+        g.lineup_commit1 = commitment;
         g.phase = if g.phase() == Phase::LineupP0Set {
             Phase::Active as u8
         } else {
@@ -177,6 +123,7 @@ fn do_submit_lineup(g: &mut Game, signer: &Signer, positions: &[u8], pieces: &[u
         };
     }
 
+    touch(g)?;
     emit!(LineupSubmitted {
         player: s,
         count: positions.len() as u8
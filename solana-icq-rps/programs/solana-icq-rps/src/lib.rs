@@ -4,58 +4,148 @@ pub mod state;
 pub use state::*;
 
 pub mod instructions;
-pub use instructions::*;
+use instructions::*;
 
 pub mod errors;
 pub mod events;
 
 declare_id!("3ueExHyxLr7ahqcBEzse3L21rTaWQ91rLtVnZLsx4ngA");
 
-#[program]
-pub mod solana_icq_rps {
+// `#[program]` in anchor 0.31 still expands to `AccountInfo::realloc` in
+// the modules it generates next to the program, so those get their own
+// scope for the allow.
+#[allow(deprecated)]
+mod entry {
     use super::*;
 
-    pub fn create_game(ctx: Context<CreateGame>, nonce: [u8; 32]) -> Result<()> {
-        create_game::create_game(ctx, nonce)?;
-        Ok(())
-    }
+    #[program]
+    pub mod solana_icq_rps {
+        use super::*;
 
-    pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
-        join_game::join_game(ctx)?;
-        Ok(())
-    }
+        pub fn create_game(
+            ctx: Context<CreateGame>,
+            nonce: [u8; 32],
+            wager: u64,
+            config: GameConfig,
+        ) -> Result<()> {
+            create_game::create_game(ctx, nonce, wager, config)?;
+            Ok(())
+        }
 
-    pub fn submit_lineup(
-        ctx: Context<SubmitLineup>,
-        positions: Vec<u8>,
-        pieces: Vec<u8>,
-    ) -> Result<()> {
-        submit_lineup::submit_lineup(ctx, positions, pieces)
-    }
-    pub fn submit_lineup_xy(
-        ctx: Context<SubmitLineupXy>,
-        xs: Vec<u8>,
-        ys: Vec<u8>,
-        pieces: Vec<u8>,
-    ) -> Result<()> {
-        submit_lineup::submit_lineup_xy(ctx, xs, ys, pieces)
-    }
+        pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
+            join_game::join_game(ctx)?;
+            Ok(())
+        }
 
-    pub fn move_piece(ctx: Context<MovePiece>, from_idx: u8, to_idx: u8) -> Result<()> {
-        move_piece::move_piece(ctx, from_idx, to_idx)
-    }
+        pub fn submit_lineup(
+            ctx: Context<SubmitLineup>,
+            positions: Vec<u8>,
+            army: [u8; ARMY_KINDS],
+            commitment: [u8; 32],
+        ) -> Result<()> {
+            submit_lineup::submit_lineup(ctx, positions, army, commitment)
+        }
+        pub fn submit_lineup_xy(
+            ctx: Context<SubmitLineupXy>,
+            xs: Vec<u8>,
+            ys: Vec<u8>,
+            army: [u8; ARMY_KINDS],
+            commitment: [u8; 32],
+        ) -> Result<()> {
+            submit_lineup::submit_lineup_xy(ctx, xs, ys, army, commitment)
+        }
 
-    pub fn move_piece_xy(
-        ctx: Context<MovePiece>,
-        from_x: u8,
-        from_y: u8,
-        to_x: u8,
-        to_y: u8,
-    ) -> Result<()> {
-        move_piece::move_piece_xy(ctx, from_x, from_y, to_x, to_y)
-    }
+        pub fn move_piece(ctx: Context<MovePiece>, from_idx: u8, to_idx: u8) -> Result<()> {
+            move_piece::move_piece(ctx, from_idx, to_idx)
+        }
+
+        pub fn move_piece_xy(
+            ctx: Context<MovePiece>,
+            from_x: u8,
+            from_y: u8,
+            to_x: u8,
+            to_y: u8,
+        ) -> Result<()> {
+            move_piece::move_piece_xy(ctx, from_x, from_y, to_x, to_y)
+        }
+
+        pub fn simulate_move(
+            ctx: Context<SimulateMove>,
+            from_idx: u8,
+            to_idx: u8,
+        ) -> Result<MovePreview> {
+            simulate_move::simulate_move(ctx, from_idx, to_idx)
+        }
+
+        pub fn reveal_piece(
+            ctx: Context<RevealPiece>,
+            idx: u8,
+            piece: u8,
+            salt: [u8; 32],
+            proof: Vec<[u8; 32]>,
+        ) -> Result<()> {
+            reveal_piece::reveal_piece(ctx, idx, piece, salt, proof)
+        }
+
+        pub fn commit_weapon(ctx: Context<ChooseWeapon>, commitment: [u8; 32]) -> Result<()> {
+            choose_weapon::commit_weapon(ctx, commitment)
+        }
+
+        pub fn reveal_weapon(ctx: Context<ChooseWeapon>, choice: u8, salt: [u8; 32]) -> Result<()> {
+            choose_weapon::reveal_weapon(ctx, choice, salt)
+        }
+
+        pub fn claim_tie_forfeit(ctx: Context<ChooseWeapon>) -> Result<()> {
+            choose_weapon::claim_tie_forfeit(ctx)
+        }
+
+        pub fn draft_pick(ctx: Context<DraftPick>, piece: u8) -> Result<()> {
+            draft::draft_pick(ctx, piece)
+        }
+
+        pub fn claim_timeout(ctx: Context<ClaimTimeout>) -> Result<()> {
+            claim_timeout::claim_timeout(ctx)
+        }
+
+        pub fn resign(ctx: Context<Resign>) -> Result<()> {
+            resign::resign(ctx)
+        }
+
+        pub fn offer_draw(ctx: Context<Draw>) -> Result<()> {
+            draw::offer_draw(ctx)
+        }
+
+        pub fn accept_draw(ctx: Context<Draw>) -> Result<()> {
+            draw::accept_draw(ctx)
+        }
+
+        pub fn decline_draw(ctx: Context<Draw>) -> Result<()> {
+            draw::decline_draw(ctx)
+        }
+
+        pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+            close_game::cancel_game(ctx)
+        }
+
+        pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+            close_game::close_game(ctx)
+        }
+
+        pub fn claim_pot(ctx: Context<ClaimPot>) -> Result<()> {
+            claim_pot::claim_pot(ctx)
+        }
+
+        pub fn init_config(ctx: Context<InitConfig>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+            admin::init_config(ctx, fee_bps, treasury)
+        }
 
-    pub fn choose_weapon(ctx: Context<ChooseWeapon>, choice: u8) -> Result<()> {
-        choose_weapon::choose_weapon(ctx, choice)
+        pub fn update_config(
+            ctx: Context<UpdateConfig>,
+            fee_bps: u16,
+            treasury: Pubkey,
+        ) -> Result<()> {
+            admin::update_config(ctx, fee_bps, treasury)
+        }
     }
 }
+pub use entry::*;
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use icq_rps_engine as engine;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Choice {
@@ -6,6 +7,8 @@ pub enum Choice {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
+    Lizard = 4,
+    Spock = 5,
 }
 
 impl From<u8> for Choice {
@@ -14,20 +17,35 @@ impl From<u8> for Choice {
             1 => Self::Rock,
             2 => Self::Paper,
             3 => Self::Scissors,
+            4 => Self::Lizard,
+            5 => Self::Spock,
             _ => Self::None,
         }
     }
 }
 
-pub fn rps_choice(a: Choice, b: Choice) -> i8 {
-    use Choice::*;
-    if a as u8 == b as u8 {
-        return 0;
+impl From<Choice> for engine::Choice {
+    fn from(v: Choice) -> Self {
+        match v {
+            Choice::None => Self::None,
+            Choice::Rock => Self::Rock,
+            Choice::Paper => Self::Paper,
+            Choice::Scissors => Self::Scissors,
+            Choice::Lizard => Self::Lizard,
+            Choice::Spock => Self::Spock,
+        }
     }
-    match (a, b) {
-        (Rock, Scissors) => 1,
-        (Scissors, Paper) => 1,
-        (Paper, Rock) => 1,
-        _ => -1,
+}
+
+impl From<engine::Choice> for Choice {
+    fn from(v: engine::Choice) -> Self {
+        match v {
+            engine::Choice::None => Self::None,
+            engine::Choice::Rock => Self::Rock,
+            engine::Choice::Paper => Self::Paper,
+            engine::Choice::Scissors => Self::Scissors,
+            engine::Choice::Lizard => Self::Lizard,
+            engine::Choice::Spock => Self::Spock,
+        }
     }
 }
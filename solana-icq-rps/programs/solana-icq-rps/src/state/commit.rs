@@ -0,0 +1,139 @@
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::MAX_CELLS;
+
+/// Levels of a lineup commitment: one leaf slot per cell of the largest
+/// board, padded to a power of two.
+pub const LINEUP_TREE_DEPTH: usize = MAX_CELLS.next_power_of_two().trailing_zeros() as usize;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Leaf of a lineup commitment: one piece at its spawn cell with its own salt.
+pub fn lineup_leaf(idx: u8, piece: u8, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[LEAF_PREFIX, idx, piece], salt]).to_bytes()
+}
+
+/// Parent of two siblings; `left` is the one at the even index.
+pub fn lineup_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[NODE_PREFIX], left, right]).to_bytes()
+}
+
+/// Checks a Merkle proof for the leaf in slot `idx` of a lineup tree. Every
+/// piece sits in the slot of its spawn cell and the bits of `idx` pick the
+/// side of each sibling, so a commitment holds exactly one piece per cell.
+pub fn verify_lineup_proof(root: &[u8; 32], idx: u8, leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    if proof.len() != LINEUP_TREE_DEPTH || (idx as usize) >= 1 << LINEUP_TREE_DEPTH {
+        return false;
+    }
+    let mut node = leaf;
+    for (level, sibling) in proof.iter().enumerate() {
+        node = if (idx >> level) & 1 == 0 {
+            lineup_node(&node, sibling)
+        } else {
+            lineup_node(sibling, &node)
+        };
+    }
+    node == *root
+}
+
+/// Commitment to a tie-break weapon: `hash(choice || salt)`.
+pub fn weapon_commitment(choice: u8, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[choice], salt]).to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Piece;
+
+    const SLOTS: usize = 1 << LINEUP_TREE_DEPTH;
+
+    /// Every level of the tree over `leaves`, leaves first.
+    fn levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| lineup_node(&pair[0], &pair[1]))
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    fn proof(levels: &[Vec<[u8; 32]>], idx: usize) -> Vec<[u8; 32]> {
+        levels[..LINEUP_TREE_DEPTH]
+            .iter()
+            .enumerate()
+            .map(|(level, nodes)| nodes[(idx >> level) ^ 1])
+            .collect()
+    }
+
+    fn root(levels: &[Vec<[u8; 32]>]) -> [u8; 32] {
+        levels[LINEUP_TREE_DEPTH][0]
+    }
+
+    const SALT: [u8; 32] = [7; 32];
+
+    fn rock_on(cell: u8) -> Vec<[u8; 32]> {
+        let mut leaves = vec![[0; 32]; SLOTS];
+        leaves[cell as usize] = lineup_leaf(cell, Piece::Rock as u8, &SALT);
+        leaves
+    }
+
+    #[test]
+    fn committed_piece_verifies_in_its_slot() {
+        let tree = levels(rock_on(30));
+        let leaf = lineup_leaf(30, Piece::Rock as u8, &SALT);
+        assert!(verify_lineup_proof(
+            &root(&tree),
+            30,
+            leaf,
+            &proof(&tree, 30)
+        ));
+    }
+
+    #[test]
+    fn forged_piece_is_rejected() {
+        let tree = levels(rock_on(30));
+        let forged = lineup_leaf(30, Piece::Paper as u8, &SALT);
+        assert!(!verify_lineup_proof(
+            &root(&tree),
+            30,
+            forged,
+            &proof(&tree, 30)
+        ));
+    }
+
+    #[test]
+    fn second_leaf_for_the_same_cell_is_rejected() {
+        // a Paper for cell 30 hidden in the unused slot of cell 0
+        let mut leaves = rock_on(30);
+        let paper = lineup_leaf(30, Piece::Paper as u8, &SALT);
+        leaves[0] = paper;
+        let tree = levels(leaves);
+        assert!(verify_lineup_proof(
+            &root(&tree),
+            0,
+            paper,
+            &proof(&tree, 0)
+        ));
+        assert!(!verify_lineup_proof(
+            &root(&tree),
+            30,
+            paper,
+            &proof(&tree, 0)
+        ));
+    }
+
+    #[test]
+    fn short_proof_is_rejected() {
+        let tree = levels(rock_on(30));
+        let leaf = lineup_leaf(30, Piece::Rock as u8, &SALT);
+        let proof = proof(&tree, 30);
+        assert!(!verify_lineup_proof(&root(&tree), 30, leaf, &proof[1..]));
+    }
+}
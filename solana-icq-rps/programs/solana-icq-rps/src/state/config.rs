@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Highest protocol fee the admin can set: 10%.
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl Config {
+    pub const SIZE: usize = 8 + 32 + 32 + 2 + 1;
+}
+
+/// The protocol's cut of `pot` at `fee_bps`.
+pub fn protocol_fee(fee_bps: u16, pot: u64) -> u64 {
+    ((pot as u128) * (fee_bps as u128) / 10_000) as u64
+}
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
+use icq_rps_engine::{self as engine, Board, Side, Tie};
 
 use crate::errors::ErrorCode;
-use crate::{BoardCellOwner, Choice, Phase, Piece};
+use crate::{BoardCellOwner, Choice, GameConfig, Phase, ARMY_KINDS, MAX_CELLS, WEAPON_KINDS};
 
-pub const WIDTH: u8 = 7;
-pub const HEIGHT: u8 = 6;
-pub const CELLS: usize = (WIDTH as usize) * (HEIGHT as usize);
+/// How long a finished game stays readable before anyone may close it.
+pub const CLOSE_GRACE_SECS: u32 = 24 * 60 * 60;
 
 #[account]
 #[derive(InitSpace)]
@@ -16,8 +16,11 @@ pub struct Game {
     pub phase: u8,
     pub is_player1_turn: bool,
 
-    pub board_cells_owner: [u8; CELLS],
-    pub board_pieces: [u8; CELLS],
+    pub config: GameConfig,
+
+    // only the first `config.cells()` entries are on the board
+    pub board_cells_owner: [u8; MAX_CELLS],
+    pub board_pieces: [u8; MAX_CELLS],
 
     pub live_player0: u16,
     pub live_player1: u16,
@@ -25,14 +28,62 @@ pub struct Game {
     pub flag_pos0: u8,
     pub flag_pos1: u8,
 
+    // hidden lineups: Merkle roots and the spawn cell each piece came from
+    pub lineup_commit0: [u8; 32],
+    pub lineup_commit1: [u8; 32],
+    pub board_origin: [u8; MAX_CELLS],
+    pub board_moved: [bool; MAX_CELLS],
+    // hidden pieces that moved like a scout
+    pub board_scouted: [bool; MAX_CELLS],
+
+    // declared army per kind, and how many of each have been revealed
+    pub army0: [u8; ARMY_KINDS],
+    pub army1: [u8; ARMY_KINDS],
+    pub revealed0: [u8; ARMY_KINDS],
+    pub revealed1: [u8; ARMY_KINDS],
+    // what is left of the draft pool
+    pub pool: [u8; ARMY_KINDS],
+
+    // battle waiting for both pieces to be revealed
+    pub battle_pending: bool,
+    pub battle_from: u8,
+    pub battle_to: u8,
+
     // tie state
     pub tie_pending: bool,
     pub tie_from: u8,
     pub tie_to: u8,
+    // weapon rounds already tied in this tie-break
+    pub tie_round: u8,
     pub choice_made0: bool,
     pub choice_made1: bool,
     pub choice0: u8,
     pub choice1: u8,
+    pub commit_made0: bool,
+    pub commit_made1: bool,
+    pub weapon_commit0: [u8; 32],
+    pub weapon_commit1: [u8; 32],
+    // tie-break weapons left when `config.limited_weapons` is on
+    pub weapon_stock0: [u8; WEAPON_KINDS],
+    pub weapon_stock1: [u8; WEAPON_KINDS],
+
+    // side with an open draw offer, as a `BoardCellOwner`
+    pub draw_offer: u8,
+
+    // unix timestamp of the last instruction, for `config` timeouts
+    pub last_action_ts: i64,
+
+    // lamports each player puts into the escrow PDA
+    pub wager: u64,
+    pub escrow_bump: u8,
+    pub pot_claimed: bool,
+    // SPL wagers; `mint` stays default for SOL games
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    // protocol fee taken from a won pot, fixed when the game is created
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
 
     pub nonce: [u8; 32],
 }
@@ -42,42 +93,130 @@ impl Game {
         Phase::from(self.phase)
     }
 
-    pub const SIZE_PLAIN: usize = 196 + 32;
+    pub const SIZE_PLAIN: usize = Self::INIT_SPACE;
 
     pub const SIZE: usize = 8 + Self::SIZE_PLAIN;
+
+    pub fn player(&self, side: Side) -> Pubkey {
+        match side {
+            Side::P0 => self.player0,
+            Side::P1 => self.player1,
+        }
+    }
+
+    /// The side `key` plays, `None` for anyone else.
+    pub fn side_of(&self, key: &Pubkey) -> Option<Side> {
+        if *key == self.player0 {
+            Some(Side::P0)
+        } else if *key == self.player1 {
+            Some(Side::P1)
+        } else {
+            None
+        }
+    }
+
+    /// The board as the rules engine sees it.
+    pub fn board(&self) -> Board {
+        let choice = |made: bool, c: u8| made.then(|| engine::Choice::from(c));
+        Board {
+            rules: self.config.rules(),
+            owner: self.board_cells_owner.map(engine::Owner::from),
+            piece: self.board_pieces.map(engine::Piece::from),
+            origin: self.board_origin,
+            moved: self.board_moved,
+            scouted: self.board_scouted,
+            live: [self.live_player0, self.live_player1],
+            flag_pos: [self.flag_pos0, self.flag_pos1],
+            army: [self.army0, self.army1],
+            revealed: [self.revealed0, self.revealed1],
+            pool: self.pool,
+            turn: if self.is_player1_turn {
+                Side::P1
+            } else {
+                Side::P0
+            },
+            battle: self
+                .battle_pending
+                .then_some((self.battle_from, self.battle_to)),
+            tie: self.tie_pending.then_some(Tie {
+                from: self.tie_from,
+                to: self.tie_to,
+                round: self.tie_round,
+            }),
+            choice: [
+                choice(self.choice_made0, self.choice0),
+                choice(self.choice_made1, self.choice1),
+            ],
+            weapon_stock: [self.weapon_stock0, self.weapon_stock1],
+        }
+    }
+
+    /// Writes back a board the rules engine has moved on.
+    pub fn store(&mut self, b: &Board) {
+        self.board_cells_owner = b.owner.map(|o| o as u8);
+        self.board_pieces = b.piece.map(|p| p as u8);
+        self.board_origin = b.origin;
+        self.board_moved = b.moved;
+        self.board_scouted = b.scouted;
+        [self.live_player0, self.live_player1] = b.live;
+        [self.flag_pos0, self.flag_pos1] = b.flag_pos;
+        [self.army0, self.army1] = b.army;
+        [self.revealed0, self.revealed1] = b.revealed;
+        self.pool = b.pool;
+        self.is_player1_turn = b.turn == Side::P1;
+        self.battle_pending = b.battle.is_some();
+        if let Some((from, to)) = b.battle {
+            self.battle_from = from;
+            self.battle_to = to;
+        }
+        self.tie_pending = b.tie.is_some();
+        self.tie_round = 0;
+        if let Some(tie) = b.tie {
+            self.tie_from = tie.from;
+            self.tie_to = tie.to;
+            self.tie_round = tie.round;
+        }
+        let [c0, c1] = b.choice;
+        self.choice_made0 = c0.is_some();
+        self.choice_made1 = c1.is_some();
+        self.choice0 = c0.map_or(Choice::None as u8, |c| c as u8);
+        self.choice1 = c1.map_or(Choice::None as u8, |c| c as u8);
+        [self.weapon_stock0, self.weapon_stock1] = b.weapon_stock;
+    }
 }
 
+/// Resets everything a game is played on, the board laid out per `g.config`.
 pub fn clear_board(g: &mut Game) {
-    g.board_cells_owner = [BoardCellOwner::None as u8; CELLS];
-    g.board_pieces = [Piece::Empty as u8; CELLS];
-    g.live_player0 = 0;
-    g.live_player1 = 0;
-    g.flag_pos0 = 255;
-    g.flag_pos1 = 255;
-    g.tie_pending = false;
+    g.store(&Board::new(g.config.rules()));
+    g.battle_from = 0;
+    g.battle_to = 0;
     g.tie_from = 0;
     g.tie_to = 0;
-    g.choice_made0 = false;
-    g.choice_made1 = false;
-    g.choice0 = Choice::None as u8;
-    g.choice1 = Choice::None as u8;
+    g.lineup_commit0 = [0; 32];
+    g.lineup_commit1 = [0; 32];
+    g.draw_offer = BoardCellOwner::None as u8;
+    g.commit_made0 = false;
+    g.commit_made1 = false;
+    g.weapon_commit0 = [0; 32];
+    g.weapon_commit1 = [0; 32];
 }
 
-pub fn validate_cell(idx: u8) -> Result<()> {
-    require!((idx as usize) < CELLS, ErrorCode::BadCell);
+/// Stamps the current time as the game's last action.
+pub fn touch(g: &mut Game) -> Result<()> {
+    g.last_action_ts = Clock::get()?.unix_timestamp;
     Ok(())
 }
 
-pub fn _y(idx: u8) -> u8 {
-    idx / WIDTH
-}
-pub fn _x(idx: u8) -> u8 {
-    idx % WIDTH
+/// True once `secs` have gone by since the last action.
+pub fn deadline_passed(g: &Game, secs: u32) -> Result<bool> {
+    let deadline = g.last_action_ts.saturating_add(secs as i64);
+    Ok(Clock::get()?.unix_timestamp > deadline)
 }
 
-pub fn is_p1_spawn(idx: u8) -> bool {
-    _y(idx) <= 1
-}
-pub fn is_p0_spawn(idx: u8) -> bool {
-    _y(idx) >= 4
+pub fn cell_at(g: &Game, x: u8, y: u8) -> Result<u8> {
+    require!(
+        x < g.config.width && y < g.config.height,
+        ErrorCode::BadCell
+    );
+    Ok(y * g.config.width + x)
 }
@@ -13,4 +13,21 @@ pub use phase::*;
 pub mod piece;
 pub use piece::*;
 
-pub const NOT_SET: u8 = 255;
+pub mod weapon;
+pub use weapon::*;
+
+pub mod stalemate;
+pub use stalemate::*;
+
+pub mod rules;
+pub use rules::*;
+
+pub mod commit;
+pub use commit::*;
+
+pub mod config;
+pub use config::*;
+
+pub use icq_rps_engine::{
+    ARMY_KINDS, MAX_CELLS, MAX_HEIGHT, MAX_WIDTH, NOT_SET, TERRAIN_BYTES, WEAPON_KINDS,
+};
@@ -5,6 +5,8 @@ pub enum BoardCellOwner {
     None = 0,
     P0 = 1,
     P1 = 2,
+    /// Terrain no piece may enter or be placed on.
+    Blocked = 3,
 }
 
 impl From<u8> for BoardCellOwner {
@@ -12,6 +14,7 @@ impl From<u8> for BoardCellOwner {
         match v {
             1 => Self::P0,
             2 => Self::P1,
+            3 => Self::Blocked,
             _ => Self::None,
         }
     }
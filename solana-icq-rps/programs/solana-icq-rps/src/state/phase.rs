@@ -8,6 +8,9 @@ pub enum Phase {
     LineupP1Set = 3,
     Active = 4,
     Finished = 5,
+    // optional draft between joining and the lineups, see `draft_pick`
+    DraftP0Turn = 6,
+    DraftP1Turn = 7,
 }
 
 impl From<u8> for Phase {
@@ -18,6 +21,8 @@ impl From<u8> for Phase {
             3 => Self::LineupP1Set,
             4 => Self::Active,
             5 => Self::Finished,
+            6 => Self::DraftP0Turn,
+            7 => Self::DraftP1Turn,
             _ => Self::Created,
         }
     }
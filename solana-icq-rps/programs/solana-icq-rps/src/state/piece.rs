@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use icq_rps_engine as engine;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Piece {
@@ -8,6 +9,13 @@ pub enum Piece {
     Scissors = 3,
     Flag = 4,
     Trap = 5,
+    Unknown = 6,
+    Lizard = 7,
+    Spock = 8,
+    /// Moves any number of empty cells in a straight line.
+    Scout = 9,
+    /// Destroys itself together with whatever attacks it.
+    Bomb = 10,
 }
 
 impl From<u8> for Piece {
@@ -18,20 +26,48 @@ impl From<u8> for Piece {
             3 => Self::Scissors,
             4 => Self::Flag,
             5 => Self::Trap,
+            6 => Self::Unknown,
+            7 => Self::Lizard,
+            8 => Self::Spock,
+            9 => Self::Scout,
+            10 => Self::Bomb,
             _ => Self::Empty,
         }
     }
 }
 
-pub fn rps(attacker: Piece, defender: Piece) -> i8 {
-    use Piece::*;
-    if attacker == defender {
-        return 0;
+impl From<Piece> for engine::Piece {
+    fn from(v: Piece) -> Self {
+        match v {
+            Piece::Empty => Self::Empty,
+            Piece::Rock => Self::Rock,
+            Piece::Paper => Self::Paper,
+            Piece::Scissors => Self::Scissors,
+            Piece::Flag => Self::Flag,
+            Piece::Trap => Self::Trap,
+            Piece::Unknown => Self::Unknown,
+            Piece::Lizard => Self::Lizard,
+            Piece::Spock => Self::Spock,
+            Piece::Scout => Self::Scout,
+            Piece::Bomb => Self::Bomb,
+        }
     }
-    match (attacker, defender) {
-        (Rock, Scissors) => 1,
-        (Scissors, Paper) => 1,
-        (Paper, Rock) => 1,
-        _ => -1,
+}
+
+impl From<engine::Piece> for Piece {
+    fn from(v: engine::Piece) -> Self {
+        match v {
+            engine::Piece::Empty => Self::Empty,
+            engine::Piece::Rock => Self::Rock,
+            engine::Piece::Paper => Self::Paper,
+            engine::Piece::Scissors => Self::Scissors,
+            engine::Piece::Flag => Self::Flag,
+            engine::Piece::Trap => Self::Trap,
+            engine::Piece::Unknown => Self::Unknown,
+            engine::Piece::Lizard => Self::Lizard,
+            engine::Piece::Spock => Self::Spock,
+            engine::Piece::Scout => Self::Scout,
+            engine::Piece::Bomb => Self::Bomb,
+        }
     }
 }
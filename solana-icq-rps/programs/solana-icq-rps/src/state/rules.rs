@@ -0,0 +1,326 @@
+use anchor_lang::prelude::*;
+use icq_rps_engine::{self as engine, Rules};
+
+use crate::errors::ErrorCode;
+use crate::{StalemateRule, Weapon, WeaponSet, ARMY_KINDS, TERRAIN_BYTES, WEAPON_KINDS};
+
+/// Impassable cells on the board.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Terrain {
+    Open = 0,
+    /// Two lake columns across the rows between the spawn zones.
+    Lakes = 1,
+    /// Cells set in `GameConfig::blocked`, one bit per cell index.
+    Custom = 2,
+}
+
+/// How a battle between two equal pieces is settled.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum TieBreakMode {
+    /// Both players pick a weapon in `commit_weapon` / `reveal_weapon`.
+    Weapons = 0,
+    /// Both pieces are removed right away.
+    BothRemoved = 1,
+    /// Like `Weapons`, but a tied round is replayed up to
+    /// `GameConfig::max_tie_rounds` rounds before both pieces are removed.
+    Repeat = 2,
+}
+
+/// How a lineup's size is constrained.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ArmyMode {
+    /// Exactly `size` pieces.
+    Composition = 0,
+    /// Up to `size` pieces whose summed `cost` stays within `budget`.
+    Budget = 1,
+}
+
+/// Lineup composition, counted per `ARMY_PIECES` kind. `min == max` pins a
+/// kind to an exact count.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ArmyRules {
+    pub mode: ArmyMode,
+    pub size: u8,
+    pub min: [u8; ARMY_KINDS],
+    pub max: [u8; ARMY_KINDS],
+    // point costs, only used in `ArmyMode::Budget`
+    pub cost: [u8; ARMY_KINDS],
+    pub budget: u16,
+    // players draft their armies in turns from `pool` before the lineups
+    pub draft: bool,
+    pub pool: [u8; ARMY_KINDS],
+}
+
+impl From<Terrain> for engine::Terrain {
+    fn from(v: Terrain) -> Self {
+        match v {
+            Terrain::Open => Self::Open,
+            Terrain::Lakes => Self::Lakes,
+            Terrain::Custom => Self::Custom,
+        }
+    }
+}
+
+impl From<engine::Terrain> for Terrain {
+    fn from(v: engine::Terrain) -> Self {
+        match v {
+            engine::Terrain::Open => Self::Open,
+            engine::Terrain::Lakes => Self::Lakes,
+            engine::Terrain::Custom => Self::Custom,
+        }
+    }
+}
+
+impl From<TieBreakMode> for engine::TieBreakMode {
+    fn from(v: TieBreakMode) -> Self {
+        match v {
+            TieBreakMode::Weapons => Self::Weapons,
+            TieBreakMode::BothRemoved => Self::BothRemoved,
+            TieBreakMode::Repeat => Self::Repeat,
+        }
+    }
+}
+
+impl From<engine::TieBreakMode> for TieBreakMode {
+    fn from(v: engine::TieBreakMode) -> Self {
+        match v {
+            engine::TieBreakMode::Weapons => Self::Weapons,
+            engine::TieBreakMode::BothRemoved => Self::BothRemoved,
+            engine::TieBreakMode::Repeat => Self::Repeat,
+        }
+    }
+}
+
+impl From<ArmyMode> for engine::ArmyMode {
+    fn from(v: ArmyMode) -> Self {
+        match v {
+            ArmyMode::Composition => Self::Composition,
+            ArmyMode::Budget => Self::Budget,
+        }
+    }
+}
+
+impl From<engine::ArmyMode> for ArmyMode {
+    fn from(v: engine::ArmyMode) -> Self {
+        match v {
+            engine::ArmyMode::Composition => Self::Composition,
+            engine::ArmyMode::Budget => Self::Budget,
+        }
+    }
+}
+
+impl From<ArmyRules> for engine::ArmyRules {
+    fn from(a: ArmyRules) -> Self {
+        Self {
+            mode: a.mode.into(),
+            size: a.size,
+            min: a.min,
+            max: a.max,
+            cost: a.cost,
+            budget: a.budget,
+            draft: a.draft,
+            pool: a.pool,
+        }
+    }
+}
+
+impl From<engine::ArmyRules> for ArmyRules {
+    fn from(a: engine::ArmyRules) -> Self {
+        Self {
+            mode: a.mode.into(),
+            size: a.size,
+            min: a.min,
+            max: a.max,
+            cost: a.cost,
+            budget: a.budget,
+            draft: a.draft,
+            pool: a.pool,
+        }
+    }
+}
+
+/// Rules chosen by the creator at `create_game` and fixed for the game.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct GameConfig {
+    pub width: u8,
+    pub height: u8,
+    /// Rows at each end of the board a player may place pieces on.
+    pub spawn_depth: u8,
+    /// Rows, counted from the inner spawn row, a trap may be placed on.
+    pub trap_rows: u8,
+    pub flag_can_move: bool,
+    /// A trap is removed after it kills its first attacker.
+    pub single_use_traps: bool,
+    pub weapons: WeaponSet,
+    /// What a scout counts as in battles.
+    pub scout_weapon: Weapon,
+    pub terrain: Terrain,
+    pub blocked: [u8; TERRAIN_BYTES],
+    pub army: ArmyRules,
+    pub tie_break: TieBreakMode,
+    pub max_tie_rounds: u8,
+    /// Each player may reveal every weapon only `weapon_stock` times.
+    pub limited_weapons: bool,
+    pub weapon_stock: [u8; WEAPON_KINDS],
+    pub stalemate: StalemateRule,
+    pub lineup_timeout_secs: u32,
+    pub turn_timeout_secs: u32,
+}
+
+/// The engine's default rules, with ten minutes for lineups and five per turn.
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self::from_rules(&Rules::default(), 600, 300)
+    }
+}
+
+impl GameConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.lineup_timeout_secs > 0 && self.turn_timeout_secs > 0,
+            ErrorCode::InvalidTimeout
+        );
+        self.rules().validate().map_err(ErrorCode::from)?;
+        Ok(())
+    }
+
+    /// The config as the rules engine sees it; timeouts only matter on-chain.
+    pub fn rules(&self) -> Rules {
+        Rules {
+            width: self.width,
+            height: self.height,
+            spawn_depth: self.spawn_depth,
+            trap_rows: self.trap_rows,
+            flag_can_move: self.flag_can_move,
+            single_use_traps: self.single_use_traps,
+            weapons: self.weapons.into(),
+            scout_weapon: self.scout_weapon.into(),
+            terrain: self.terrain.into(),
+            blocked: self.blocked,
+            army: self.army.into(),
+            tie_break: self.tie_break.into(),
+            max_tie_rounds: self.max_tie_rounds,
+            limited_weapons: self.limited_weapons,
+            weapon_stock: self.weapon_stock,
+            stalemate: self.stalemate.into(),
+        }
+    }
+
+    /// `rules` with the on-chain timeouts added.
+    pub fn from_rules(r: &Rules, lineup_timeout_secs: u32, turn_timeout_secs: u32) -> Self {
+        Self {
+            width: r.width,
+            height: r.height,
+            spawn_depth: r.spawn_depth,
+            trap_rows: r.trap_rows,
+            flag_can_move: r.flag_can_move,
+            single_use_traps: r.single_use_traps,
+            weapons: r.weapons.into(),
+            scout_weapon: r.scout_weapon.into(),
+            terrain: r.terrain.into(),
+            blocked: r.blocked,
+            army: r.army.into(),
+            tie_break: r.tie_break.into(),
+            max_tie_rounds: r.max_tie_rounds,
+            limited_weapons: r.limited_weapons,
+            weapon_stock: r.weapon_stock,
+            stalemate: r.stalemate.into(),
+            lineup_timeout_secs,
+            turn_timeout_secs,
+        }
+    }
+
+    pub fn cells(&self) -> usize {
+        (self.width as usize) * (self.height as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Choice, Piece};
+    use core::fmt::Debug;
+
+    /// Every engine value decoded from a byte converts to its program twin
+    /// and back unchanged, keeping its discriminant on the way.
+    fn check_twin<E, O>(decode: impl Fn(u8) -> E, tag: impl Fn(E) -> u8, our_tag: impl Fn(O) -> u8)
+    where
+        E: Copy + PartialEq + Debug + Into<O>,
+        O: Copy + Into<E>,
+    {
+        for v in 0..=u8::MAX {
+            let e = decode(v);
+            let ours: O = e.into();
+            assert_eq!(our_tag(ours), tag(e), "{e:?}");
+            assert_eq!(ours.into(), e);
+        }
+    }
+
+    #[test]
+    fn every_engine_value_has_a_program_twin() {
+        check_twin::<engine::Piece, Piece>(engine::Piece::from, |e| e as u8, |o| o as u8);
+        check_twin::<engine::Choice, Choice>(engine::Choice::from, |e| e as u8, |o| o as u8);
+        check_twin::<engine::Weapon, Weapon>(engine::Weapon::from, |e| e as u8, |o| o as u8);
+        check_twin::<engine::WeaponSet, WeaponSet>(
+            engine::WeaponSet::from,
+            |e| e as u8,
+            |o| o as u8,
+        );
+        check_twin::<engine::Terrain, Terrain>(engine::Terrain::from, |e| e as u8, |o| o as u8);
+        check_twin::<engine::TieBreakMode, TieBreakMode>(
+            engine::TieBreakMode::from,
+            |e| e as u8,
+            |o| o as u8,
+        );
+        check_twin::<engine::ArmyMode, ArmyMode>(engine::ArmyMode::from, |e| e as u8, |o| o as u8);
+        check_twin::<engine::StalemateRule, StalemateRule>(
+            engine::StalemateRule::from,
+            |e| e as u8,
+            |o| o as u8,
+        );
+    }
+
+    #[test]
+    fn default_config_plays_the_engine_defaults() {
+        let config = GameConfig::default();
+        assert_eq!(config.rules(), Rules::default());
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(
+            GameConfig::from_rules(&config.rules(), 1, 2),
+            GameConfig {
+                lineup_timeout_secs: 1,
+                turn_timeout_secs: 2,
+                ..config
+            }
+        );
+    }
+
+    #[test]
+    fn custom_config_survives_the_trip_through_the_engine() {
+        let config = GameConfig {
+            width: 10,
+            height: 8,
+            spawn_depth: 3,
+            weapons: WeaponSet::Extended,
+            scout_weapon: Weapon::Spock,
+            terrain: Terrain::Lakes,
+            tie_break: TieBreakMode::Repeat,
+            max_tie_rounds: 3,
+            limited_weapons: true,
+            weapon_stock: [1, 2, 3, 4, 5],
+            stalemate: StalemateRule::Draw,
+            army: ArmyRules {
+                mode: ArmyMode::Budget,
+                budget: 40,
+                cost: [1, 2, 3, 0, 4, 5, 6, 7, 8],
+                ..GameConfig::default().army
+            },
+            ..GameConfig::default()
+        };
+        let timeouts = (config.lineup_timeout_secs, config.turn_timeout_secs);
+        assert_eq!(
+            GameConfig::from_rules(&config.rules(), timeouts.0, timeouts.1),
+            config
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use icq_rps_engine as engine;
+
+/// What happens when the player to move has no legal move left.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum StalemateRule {
+    Loss = 0,
+    Draw = 1,
+}
+
+impl From<u8> for StalemateRule {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => Self::Draw,
+            _ => Self::Loss,
+        }
+    }
+}
+
+impl From<StalemateRule> for engine::StalemateRule {
+    fn from(v: StalemateRule) -> Self {
+        match v {
+            StalemateRule::Loss => Self::Loss,
+            StalemateRule::Draw => Self::Draw,
+        }
+    }
+}
+
+impl From<engine::StalemateRule> for StalemateRule {
+    fn from(v: engine::StalemateRule) -> Self {
+        match v {
+            engine::StalemateRule::Loss => Self::Loss,
+            engine::StalemateRule::Draw => Self::Draw,
+        }
+    }
+}
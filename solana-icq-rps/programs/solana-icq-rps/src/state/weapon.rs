@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use icq_rps_engine as engine;
+
+/// The weapons behind both pieces and tie-break choices.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Weapon {
+    Rock = 0,
+    Paper = 1,
+    Scissors = 2,
+    Lizard = 3,
+    Spock = 4,
+}
+
+/// Which weapons a game is played with.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum WeaponSet {
+    Classic = 0,
+    /// Rock, Paper, Scissors, Lizard, Spock.
+    Extended = 1,
+}
+
+impl From<Weapon> for engine::Weapon {
+    fn from(v: Weapon) -> Self {
+        match v {
+            Weapon::Rock => Self::Rock,
+            Weapon::Paper => Self::Paper,
+            Weapon::Scissors => Self::Scissors,
+            Weapon::Lizard => Self::Lizard,
+            Weapon::Spock => Self::Spock,
+        }
+    }
+}
+
+impl From<engine::Weapon> for Weapon {
+    fn from(v: engine::Weapon) -> Self {
+        match v {
+            engine::Weapon::Rock => Self::Rock,
+            engine::Weapon::Paper => Self::Paper,
+            engine::Weapon::Scissors => Self::Scissors,
+            engine::Weapon::Lizard => Self::Lizard,
+            engine::Weapon::Spock => Self::Spock,
+        }
+    }
+}
+
+impl From<WeaponSet> for engine::WeaponSet {
+    fn from(v: WeaponSet) -> Self {
+        match v {
+            WeaponSet::Classic => Self::Classic,
+            WeaponSet::Extended => Self::Extended,
+        }
+    }
+}
+
+impl From<engine::WeaponSet> for WeaponSet {
+    fn from(v: engine::WeaponSet) -> Self {
+        match v {
+            engine::WeaponSet::Classic => Self::Classic,
+            engine::WeaponSet::Extended => Self::Extended,
+        }
+    }
+}
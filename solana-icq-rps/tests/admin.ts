@@ -0,0 +1,58 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import { expect } from 'chai';
+import { SolanaIcqRps } from '../target/types/solana_icq_rps';
+import { airdropIfNeeded } from './pdas';
+import { ensureConfig, FEE_BPS } from './setupGame';
+import { expectError } from './errors';
+
+const MAX_FEE_BPS = 1_000;
+
+// The admin can change the fee and treasury up to the cap; anybody else is
+// turned away. The config is restored afterwards for the other tests.
+export const adminUpdatesConfig = async () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.solanaIcqRps as Program<SolanaIcqRps>;
+  const admin = (provider.wallet as anchor.Wallet).publicKey;
+  const config = await ensureConfig(program);
+
+  const stranger = Keypair.generate();
+  await airdropIfNeeded(provider.connection, stranger.publicKey);
+  const treasury = Keypair.generate().publicKey;
+
+  await program.methods
+    .updateConfig(MAX_FEE_BPS, treasury)
+    .accountsStrict({ config, admin })
+    .rpc();
+  let c = await program.account.config.fetch(config);
+  expect(c.feeBps).to.equal(MAX_FEE_BPS);
+  expect(c.treasury.toBase58()).to.equal(treasury.toBase58());
+  expect(c.admin.toBase58()).to.equal(admin.toBase58());
+
+  await expectError(
+    program.methods
+      .updateConfig(MAX_FEE_BPS + 1, treasury)
+      .accountsStrict({ config, admin })
+      .rpc(),
+    'FeeTooHigh',
+  );
+
+  await expectError(
+    program.methods
+      .updateConfig(0, stranger.publicKey)
+      .accountsStrict({ config, admin: stranger.publicKey })
+      .signers([stranger])
+      .rpc(),
+    'NotAdmin',
+  );
+
+  await program.methods
+    .updateConfig(FEE_BPS, admin)
+    .accountsStrict({ config, admin })
+    .rpc();
+  c = await program.account.config.fetch(config);
+  expect(c.feeBps).to.equal(FEE_BPS);
+  expect(c.treasury.toBase58()).to.equal(admin.toBase58());
+};
@@ -0,0 +1,66 @@
+import { expect } from 'chai';
+import { CLASSIC_CONFIG } from './setupGame';
+import { startCustomGame } from './customGame';
+import { decodeGame } from './cells';
+import { sleep } from './errors';
+import { Phase, Piece } from './types';
+
+// A 7x4 board with one spawn row each and flagless budget armies, so a
+// game can run down to its very last pieces.
+const BOMB_CONFIG = {
+  ...CLASSIC_CONFIG,
+  height: 4,
+  spawnDepth: 1,
+  trapRows: 1,
+  army: {
+    ...CLASSIC_CONFIG.army,
+    mode: { budget: {} },
+    size: 2,
+    min: [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    max: [2, 2, 2, 0, 0, 0, 0, 0, 1],
+    cost: [1, 1, 1, 0, 0, 0, 0, 0, 1],
+    budget: 2,
+  },
+};
+
+// p1's scissors dies attacking p0's lone rock, which then walks into p1's
+// bomb: the bomb goes off and takes the last piece of both sides, a draw.
+export const bombTakesTheLastPieces = async () => {
+  const { program, move, reveal, fetch } = await startCustomGame(
+    BOMB_CONFIG,
+    [[4, 3, Piece.Rock]],
+    [
+      [4, 0, Piece.Bomb],
+      [5, 0, Piece.Scissors],
+    ],
+  );
+
+  const explosions: any[] = [];
+  const listener = program.addEventListener('bombExploded', (e) =>
+    explosions.push(e),
+  );
+
+  await move('p0', 4, 3, 4, 2);
+  await move('p1', 5, 0, 5, 1);
+  await move('p0', 4, 2, 4, 1);
+  await move('p1', 5, 1, 4, 1);
+  await reveal();
+
+  let g = decodeGame(await fetch());
+  expect([g.live0, g.live1]).to.deep.equal([1, 1]);
+  expect(g.phase).to.equal(Phase.Active);
+
+  await move('p0', 4, 1, 4, 0);
+  await reveal();
+
+  g = decodeGame(await fetch());
+  expect([g.live0, g.live1]).to.deep.equal([0, 0]);
+  expect(g.phase).to.equal(Phase.Finished);
+  expect(g.winner).to.be.null;
+
+  // events arrive over the websocket after the transaction lands
+  await sleep(1000);
+  await program.removeEventListener(listener);
+  expect(explosions).to.have.length(1);
+  expect(explosions[0].attacker).to.have.property('rock');
+};
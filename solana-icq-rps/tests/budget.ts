@@ -0,0 +1,53 @@
+import { expect } from 'chai';
+import { CLASSIC_CONFIG } from './setupGame';
+import { createCustomGame, Placement } from './customGame';
+import { expectError } from './errors';
+import { Phase, Piece } from './types';
+
+// up to 14 pieces worth at most 10 points; flags are free
+const BUDGET_CONFIG = {
+  ...CLASSIC_CONFIG,
+  army: {
+    ...CLASSIC_CONFIG.army,
+    mode: { budget: {} },
+    cost: [2, 2, 2, 0, 3, 0, 0, 0, 0],
+    budget: 10,
+  },
+};
+
+// A lineup over budget is refused; a smaller army within it is accepted
+// and the game starts with fewer pieces than the army size.
+export const budgetArmies = async () => {
+  const { submitLineup, fetch } = await createCustomGame(BUDGET_CONFIG);
+
+  // a flag and six rocks cost 12 points
+  const tooDear: Placement[] = [
+    [3, 5, Piece.Flag],
+    ...[0, 1, 2, 3, 4, 5].map((x): Placement => [x, 4, Piece.Rock]),
+  ];
+  await expectError(submitLineup('p0', tooDear), 'OverBudget');
+
+  // a flag, a trap and one of each weapon cost 9
+  await submitLineup('p0', [
+    [3, 5, Piece.Flag],
+    [2, 4, Piece.Trap],
+    [0, 4, Piece.Rock],
+    [1, 4, Piece.Paper],
+    [4, 4, Piece.Scissors],
+  ]);
+  await submitLineup('p1', [
+    [3, 0, Piece.Flag],
+    [0, 1, Piece.Rock],
+    [1, 1, Piece.Rock],
+    [2, 1, Piece.Paper],
+    [4, 1, Piece.Scissors],
+    [5, 1, Piece.Scissors],
+  ]);
+
+  const g = await fetch();
+  expect(g.phase).to.equal(Phase.Active);
+  expect(g.army0).to.deep.equal([1, 1, 1, 1, 1, 0, 0, 0, 0]);
+  expect(g.army1).to.deep.equal([2, 1, 2, 1, 0, 0, 0, 0, 0]);
+  expect(Number(g.livePlayer0)).to.equal(5);
+  expect(Number(g.livePlayer1)).to.equal(6);
+};
@@ -0,0 +1,73 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import { SolanaIcqRps } from '../target/types/solana_icq_rps';
+import { CLASSIC_CONFIG, ensureConfig, escrowPda, NO_TOKEN } from './setupGame';
+import {
+  createTokenAccount,
+  mintTo,
+  tokenBalance,
+  TOKEN_PROGRAM_ID,
+  vaultPda,
+} from './token';
+const { randomBytes } = require('crypto');
+
+export const cancelGame = async (wager = 0, mint?: PublicKey) => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.solanaIcqRps as Program<SolanaIcqRps>;
+  const p0 = (provider.wallet as anchor.Wallet).publicKey;
+
+  const nonce = randomBytes(32);
+  const [game] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('game'), p0.toBuffer(), Buffer.from(nonce)],
+    program.programId,
+  );
+  const escrow = escrowPda(program, game);
+  const config = await ensureConfig(program);
+
+  const vault = vaultPda(program, game);
+  const token = mint && {
+    accounts: { mint, vault, tokenProgram: TOKEN_PROGRAM_ID },
+    payerToken: await createTokenAccount(program, mint, p0),
+  };
+  if (token) {
+    await mintTo(program, mint, token.payerToken, wager);
+  }
+
+  await program.methods
+    .createGame([...nonce], new anchor.BN(wager), CLASSIC_CONFIG)
+    .accountsStrict({
+      game,
+      config,
+      escrow,
+      payer: p0,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...(token?.accounts ?? NO_TOKEN),
+      payerToken: token?.payerToken ?? null,
+    })
+    .rpc();
+  if (token) {
+    expect(await tokenBalance(program, vault)).to.equal(wager);
+  }
+
+  await program.methods
+    .cancelGame()
+    .accountsStrict({
+      game,
+      escrow,
+      payer: p0,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...(token?.accounts ?? NO_TOKEN),
+      payerToken: token?.payerToken ?? null,
+    })
+    .rpc();
+
+  expect(await program.account.game.fetchNullable(game)).to.be.null;
+  expect(await provider.connection.getBalance(escrow)).to.equal(0);
+  if (token) {
+    expect(await tokenBalance(program, token.payerToken)).to.equal(wager);
+    expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+  }
+};
@@ -21,11 +21,26 @@ export const padPiecesRPS = (len: number) => {
   return out;
 };
 
+// declared army, in the program's `ARMY_PIECES` order
+const ARMY_PIECES = [
+  Piece.Rock,
+  Piece.Paper,
+  Piece.Scissors,
+  Piece.Flag,
+  Piece.Trap,
+  Piece.Lizard,
+  Piece.Spock,
+  Piece.Scout,
+  Piece.Bomb,
+];
+export const armyCounts = (pcs: number[]) =>
+  ARMY_PIECES.map((p) => pcs.filter((x) => x === p).length);
+
 export const buildFullLineupWithFlag = (
   isP0: boolean,
   flagIdx: number,
   trapIdx: number | null,
-): { xs: number[]; ys: number[]; pcs: number[] } => {
+): { xs: number[]; ys: number[]; pcs: number[]; cells: number[] } => {
   const baseCells = spawnCells(isP0);
 
   const forcedCells: number[] = [flagIdx];
@@ -61,7 +76,7 @@ export const buildFullLineupWithFlag = (
     ys.push(y);
   }
 
-  return { xs, ys, pcs: allPieces };
+  return { xs, ys, pcs: allPieces, cells: allCells };
 };
 
 export const u8 = (arr: number[]) => Buffer.from(Uint8Array.from(arr));
@@ -76,6 +91,7 @@ export function board2D<T>(flat: T[]): T[][] {
 export const printBoard = (owners: Owner[], pieces: Piece[]) => {
   const sym = (o: Owner, p: Piece) => {
     if (o === Owner.None) return ' . ';
+    if (o === Owner.Blocked) return ' # ';
     const base =
       p === Piece.Rock
         ? 'R'
@@ -87,6 +103,16 @@ export const printBoard = (owners: Owner[], pieces: Piece[]) => {
         ? 'F'
         : p === Piece.Trap
         ? 'T'
+        : p === Piece.Lizard
+        ? 'L'
+        : p === Piece.Spock
+        ? 'V'
+        : p === Piece.Scout
+        ? 'C'
+        : p === Piece.Bomb
+        ? 'B'
+        : p === Piece.Unknown
+        ? '?'
         : ' . ';
     return o === Owner.P0 ? ` ${base.toLowerCase()} ` : ` ${base} `;
   };
@@ -98,10 +124,13 @@ export const printBoard = (owners: Owner[], pieces: Piece[]) => {
 };
 
 export const decodeGame = (raw: any) => {
-  const owners: Owner[] = (raw.boardCellsOwner as number[]).map(
-    (n) => n as Owner,
-  );
-  const pieces: Piece[] = (raw.boardPieces as number[]).map((n) => n as Piece);
+  // the on-chain arrays are sized for the largest board
+  const owners: Owner[] = (raw.boardCellsOwner as number[])
+    .slice(0, CELLS)
+    .map((n) => n as Owner);
+  const pieces: Piece[] = (raw.boardPieces as number[])
+    .slice(0, CELLS)
+    .map((n) => n as Piece);
 
   return {
     p0: raw.player0 as string,
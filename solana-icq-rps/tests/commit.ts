@@ -0,0 +1,87 @@
+import * as anchor from '@coral-xyz/anchor';
+import { createHash, randomBytes } from 'crypto';
+import { Owner, Piece } from './types';
+
+export interface LineupLeaf {
+  piece: number;
+  salt: Buffer;
+  proof: number[][];
+}
+
+export interface LineupSecret {
+  root: number[];
+  // keyed by spawn cell index
+  leaves: Map<number, LineupLeaf>;
+}
+
+const sha256 = (...parts: Buffer[]) =>
+  createHash('sha256').update(Buffer.concat(parts)).digest();
+
+const leafHash = (idx: number, piece: number, salt: Buffer) =>
+  sha256(Buffer.from([0, idx, piece]), salt);
+
+const nodeHash = (left: Buffer, right: Buffer) =>
+  sha256(Buffer.from([1]), left, right);
+
+// one leaf slot per cell of the largest (10x10) board, padded to 128
+export const LINEUP_TREE_DEPTH = 7;
+
+// Merkle tree with every piece in the slot of its spawn cell and zeroed
+// unused slots, matching `verify_lineup_proof` on chain.
+export const commitLineup = (
+  cells: number[],
+  pieces: number[],
+): LineupSecret => {
+  const salts = cells.map(() => randomBytes(32));
+  let level: Buffer[] = new Array(1 << LINEUP_TREE_DEPTH).fill(
+    Buffer.alloc(32),
+  );
+  cells.forEach((c, i) => (level[c] = leafHash(c, pieces[i], salts[i])));
+  const proofs: Buffer[][] = cells.map(() => []);
+
+  for (let depth = 0; depth < LINEUP_TREE_DEPTH; depth++) {
+    cells.forEach((c, k) => proofs[k].push(level[(c >> depth) ^ 1]));
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(nodeHash(level[i], level[i + 1]));
+    }
+    level = next;
+  }
+
+  const leaves = new Map<number, LineupLeaf>();
+  cells.forEach((c, i) =>
+    leaves.set(c, {
+      piece: pieces[i],
+      salt: salts[i],
+      proof: proofs[i].map((b) => [...b]),
+    }),
+  );
+  return { root: [...level[0]], leaves };
+};
+
+export const weaponCommitment = (choice: number, salt: Buffer) => [
+  ...sha256(Buffer.from([choice]), salt),
+];
+
+// Reveals every still-hidden piece of the pending battle, if there is one.
+export const revealBattle = async (
+  program: anchor.Program<any>,
+  game: anchor.web3.PublicKey,
+  p0: anchor.web3.PublicKey,
+  p1: anchor.web3.Keypair,
+  lineup0: LineupSecret,
+  lineup1: LineupSecret,
+) => {
+  const g: any = await program.account.game.fetch(game);
+  if (!g.battlePending) return;
+
+  for (const cell of [g.battleFrom, g.battleTo]) {
+    if (g.boardPieces[cell] !== Piece.Unknown) continue;
+    const isP0 = g.boardCellsOwner[cell] === Owner.P0;
+    const leaf = (isP0 ? lineup0 : lineup1).leaves.get(g.boardOrigin[cell])!;
+    const call = program.methods
+      .revealPiece(cell, leaf.piece, [...leaf.salt], leaf.proof)
+      .accountsStrict({ game, signer: isP0 ? p0 : p1.publicKey });
+    await (isP0 ? call : call.signers([p1])).rpc();
+  }
+};
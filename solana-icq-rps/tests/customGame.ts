@@ -0,0 +1,134 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import { SolanaIcqRps } from '../target/types/solana_icq_rps';
+import { airdropIfNeeded } from './pdas';
+import { ensureConfig, escrowPda, NO_TOKEN } from './setupGame';
+import { armyCounts, toIdx, u8 } from './cells';
+import { commitLineup, LineupSecret, revealBattle } from './commit';
+const { randomBytes } = require('crypto');
+
+// a piece placed at (x, y) on the 7x6 board
+export type Placement = [number, number, number];
+export type Player = 'p0' | 'p1';
+
+// A joined game played with `config`. Both lineups are chosen by the test,
+// so every feature test can set up exactly the position it needs.
+export const createCustomGame = async (config: any) => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.solanaIcqRps as Program<SolanaIcqRps>;
+  const p0 = (provider.wallet as anchor.Wallet).publicKey;
+  const p1 = Keypair.generate();
+  await airdropIfNeeded(provider.connection, p0);
+  await airdropIfNeeded(provider.connection, p1.publicKey);
+
+  const nonce = randomBytes(32);
+  const [game] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('game'), p0.toBuffer(), Buffer.from(nonce)],
+    program.programId,
+  );
+  const escrow = escrowPda(program, game);
+
+  await program.methods
+    .createGame([...nonce], new anchor.BN(0), config)
+    .accountsStrict({
+      game,
+      config: await ensureConfig(program),
+      escrow,
+      payer: p0,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...NO_TOKEN,
+      payerToken: null,
+    })
+    .rpc();
+
+  await program.methods
+    .joinGame()
+    .accountsStrict({
+      game,
+      escrow,
+      joiner: p1.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...NO_TOKEN,
+      joinerToken: null,
+    })
+    .signers([p1])
+    .rpc();
+
+  // signs `method` (built on `{ game, signer }` accounts) as `who`
+  const as = (who: Player, method: any) =>
+    who === 'p0'
+      ? method.accountsStrict({ game, signer: p0 }).rpc()
+      : method
+          .accountsStrict({ game, signer: p1.publicKey })
+          .signers([p1])
+          .rpc();
+
+  const lineups: Partial<Record<Player, LineupSecret>> = {};
+
+  const submitLineup = async (who: Player, placements: Placement[]) => {
+    const cells = placements.map(([x, y]) => toIdx(x, y));
+    const pcs = placements.map(([, , piece]) => piece);
+    const lineup = commitLineup(cells, pcs);
+    await program.methods
+      .submitLineupXy(
+        u8(placements.map(([x]) => x)),
+        u8(placements.map(([, y]) => y)),
+        armyCounts(pcs),
+        lineup.root,
+      )
+      .accountsStrict({
+        inner: { game, signer: who === 'p0' ? p0 : p1.publicKey },
+      })
+      .signers(who === 'p0' ? [] : [p1])
+      .rpc();
+    lineups[who] = lineup;
+  };
+
+  const move = (who: Player, fx: number, fy: number, tx: number, ty: number) =>
+    as(who, program.methods.movePieceXy(fx, fy, tx, ty));
+
+  // reveals whatever the pending battle still hides
+  const reveal = () =>
+    revealBattle(program, game, p0, p1, lineups.p0!, lineups.p1!);
+
+  const fetch = () => program.account.game.fetch(game);
+
+  // reveals the piece on (x, y) alone, for battles that end on a reveal
+  const revealAt = async (who: Player, x: number, y: number) => {
+    const cell = toIdx(x, y);
+    const origin = (await fetch()).boardOrigin[cell];
+    const leaf = lineups[who]!.leaves.get(origin)!;
+    await as(
+      who,
+      program.methods.revealPiece(cell, leaf.piece, [...leaf.salt], leaf.proof),
+    );
+  };
+
+  return {
+    program,
+    p0,
+    p1,
+    game,
+    escrow,
+    as,
+    submitLineup,
+    move,
+    reveal,
+    revealAt,
+    fetch,
+  };
+};
+
+// A custom game with both lineups in, p0 to move.
+export const startCustomGame = async (
+  config: any,
+  lineup0: Placement[],
+  lineup1: Placement[],
+) => {
+  const g = await createCustomGame(config);
+  await g.submitLineup('p0', lineup0);
+  await g.submitLineup('p1', lineup1);
+  return g;
+};
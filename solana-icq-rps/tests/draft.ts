@@ -0,0 +1,99 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import { expect } from 'chai';
+import { SolanaIcqRps } from '../target/types/solana_icq_rps';
+import { airdropIfNeeded } from './pdas';
+import { CLASSIC_CONFIG, ensureConfig, escrowPda, NO_TOKEN } from './setupGame';
+import { Phase, Piece } from './types';
+const { randomBytes } = require('crypto');
+
+// three pieces each: one flag and two of rock/paper/scissors
+const DRAFT_CONFIG = {
+  ...CLASSIC_CONFIG,
+  army: {
+    ...CLASSIC_CONFIG.army,
+    size: 3,
+    max: [3, 3, 3, 1, 0, 0, 0, 0, 0],
+    draft: true,
+    pool: [2, 2, 2, 2, 0, 0, 0, 0, 0],
+  },
+};
+
+export const draftArmies = async () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.solanaIcqRps as Program<SolanaIcqRps>;
+  const p0 = (provider.wallet as anchor.Wallet).publicKey;
+  const p1 = Keypair.generate();
+  await airdropIfNeeded(provider.connection, p1.publicKey);
+
+  const nonce = randomBytes(32);
+  const [game] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('game'), p0.toBuffer(), Buffer.from(nonce)],
+    program.programId,
+  );
+  const escrow = escrowPda(program, game);
+  const config = await ensureConfig(program);
+
+  await program.methods
+    .createGame([...nonce], new anchor.BN(0), DRAFT_CONFIG)
+    .accountsStrict({
+      game,
+      config,
+      escrow,
+      payer: p0,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...NO_TOKEN,
+      payerToken: null,
+    })
+    .rpc();
+
+  await program.methods
+    .joinGame()
+    .accountsStrict({
+      game,
+      escrow,
+      joiner: p1.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...NO_TOKEN,
+      joinerToken: null,
+    })
+    .signers([p1])
+    .rpc();
+  expect((await program.account.game.fetch(game)).phase).to.equal(
+    Phase.DraftP1Turn,
+  );
+
+  const pick = (who: 'p0' | 'p1', piece: number) => {
+    const m = program.methods.draftPick(piece);
+    return who === 'p0'
+      ? m.accountsStrict({ game, signer: p0 }).rpc()
+      : m
+          .accountsStrict({ game, signer: p1.publicKey })
+          .signers([p1])
+          .rpc();
+  };
+
+  // player1 opens the draft
+  let outOfTurn = false;
+  try {
+    await pick('p0', Piece.Flag);
+  } catch (e) {
+    outOfTurn = true;
+  }
+  expect(outOfTurn).to.equal(true);
+
+  await pick('p1', Piece.Flag);
+  await pick('p0', Piece.Flag);
+  await pick('p1', Piece.Rock);
+  await pick('p0', Piece.Rock);
+  await pick('p1', Piece.Paper);
+  await pick('p0', Piece.Scissors);
+
+  const g = await program.account.game.fetch(game);
+  expect(g.phase).to.equal(Phase.Joined);
+  expect(g.army0).to.deep.equal([1, 0, 1, 1, 0, 0, 0, 0, 0]);
+  expect(g.army1).to.deep.equal([1, 1, 0, 1, 0, 0, 0, 0, 0]);
+  expect(g.pool).to.deep.equal([0, 1, 1, 0, 0, 0, 0, 0, 0]);
+};
@@ -0,0 +1,80 @@
+import { expect } from 'chai';
+import { submitFixedLineup } from './submitFixedLineup';
+import { decodeGame } from './cells';
+import { Phase } from './types';
+import { expectError, sleep } from './errors';
+import { FAST_CONFIG, WAIT_MS } from './timeout';
+
+export const mutualDraw = async () => {
+  const { program, p0, p1, game } = await submitFixedLineup();
+
+  await program.methods
+    .offerDraw()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  await program.methods
+    .acceptDraw()
+    .accountsStrict({ game, signer: p1.publicKey })
+    .signers([p1])
+    .rpc();
+
+  const decoded = decodeGame(await program.account.game.fetch(game));
+  expect(decoded.phase).to.equal(Phase.Finished);
+  expect(decoded.winner).to.be.null;
+};
+
+// An offer only holds for the position it was made in: once a piece moves,
+// the opponent can no longer accept it.
+export const drawOfferLapsesOnMove = async () => {
+  const { program, p0, p1, game } = await submitFixedLineup();
+
+  await program.methods
+    .offerDraw()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  // p0's front row moves one step forward
+  await program.methods
+    .movePieceXy(0, 4, 0, 3)
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  await expectError(
+    program.methods
+      .acceptDraw()
+      .accountsStrict({ game, signer: p1.publicKey })
+      .signers([p1])
+      .rpc(),
+    'NoDrawOffer',
+  );
+  const decoded = decodeGame(await program.account.game.fetch(game));
+  expect(decoded.phase).to.equal(Phase.Active);
+};
+
+// Offering and withdrawing a draw does not restart the turn clock, so the
+// player to move cannot stall with it.
+export const drawOfferKeepsTheTurnClock = async () => {
+  const { program, p0, p1, game } = await submitFixedLineup(0, FAST_CONFIG);
+
+  await program.methods
+    .offerDraw()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+  await sleep(WAIT_MS);
+  await program.methods
+    .declineDraw()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  // the turn ran out before the offer was withdrawn
+  await program.methods
+    .claimTimeout()
+    .accountsStrict({ game, signer: p1.publicKey })
+    .signers([p1])
+    .rpc();
+
+  const decoded = decodeGame(await program.account.game.fetch(game));
+  expect(decoded.phase).to.equal(Phase.Finished);
+  expect(decoded.winner!.toString()).to.equal(p1.publicKey.toString());
+};
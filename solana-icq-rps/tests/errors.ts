@@ -0,0 +1,16 @@
+import { expect } from 'chai';
+
+// Runs `call` and checks that the program rejected it with `code`, the
+// `ErrorCode` variant name.
+export const expectError = async (call: Promise<unknown>, code: string) => {
+  let err: any = null;
+  try {
+    await call;
+  } catch (e) {
+    err = e;
+  }
+  expect(err, `expected ${code}`).to.not.be.null;
+  expect(err.error?.errorCode?.code ?? err.toString()).to.equal(code);
+};
+
+export const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
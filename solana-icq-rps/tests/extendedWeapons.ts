@@ -0,0 +1,46 @@
+import { expect } from 'chai';
+import { CLASSIC_CONFIG } from './setupGame';
+import { startCustomGame } from './customGame';
+import { decodeGame, toIdx } from './cells';
+import { Owner, Piece } from './types';
+
+// Rock-Paper-Scissors-Lizard-Spock with three-piece armies
+const EXTENDED_CONFIG = {
+  ...CLASSIC_CONFIG,
+  weapons: { extended: {} },
+  army: {
+    ...CLASSIC_CONFIG.army,
+    size: 3,
+    max: [3, 3, 3, 1, 0, 3, 3, 0, 0],
+  },
+};
+
+// A hidden Lizard attacks a hidden Spock and wins once both are revealed,
+// as the extended `BEATS` table says.
+export const lizardPoisonsSpock = async () => {
+  const { move, reveal, fetch } = await startCustomGame(
+    EXTENDED_CONFIG,
+    [
+      [3, 5, Piece.Flag],
+      [3, 4, Piece.Lizard],
+      [0, 4, Piece.Spock],
+    ],
+    [
+      [3, 0, Piece.Flag],
+      [3, 1, Piece.Spock],
+      [0, 1, Piece.Rock],
+    ],
+  );
+
+  await move('p0', 3, 4, 3, 3);
+  await move('p1', 3, 1, 3, 2);
+  await move('p0', 3, 3, 3, 2);
+  await reveal();
+
+  const g = decodeGame(await fetch());
+  expect(g.owners[toIdx(3, 2)]).to.equal(Owner.P0);
+  expect(g.pieces[toIdx(3, 2)]).to.equal(Piece.Lizard);
+  expect(g.live0).to.equal(3);
+  expect(g.live1).to.equal(2);
+  expect(g.isP1Turn).to.equal(true);
+};
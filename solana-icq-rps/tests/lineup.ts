@@ -0,0 +1,43 @@
+import { setupGame } from './setupGame';
+import { commitLineup } from './commit';
+import { armyCounts, buildFullLineupWithFlag, toIdx, u8 } from './cells';
+import { expectError } from './errors';
+import { Piece } from './types';
+
+// Lineups that break the army rules are refused with the code of the
+// offending piece kind.
+export const lineupNamesTheBadPieceKind = async () => {
+  const { program, p1, game } = await setupGame();
+
+  const lineup = buildFullLineupWithFlag(
+    /* isP0 */ false,
+    toIdx(3, 0),
+    toIdx(4, 1),
+  );
+  const submit = (pcs: number[]) => {
+    const { xs, ys, cells } = lineup;
+    return program.methods
+      .submitLineupXy(
+        u8(xs),
+        u8(ys),
+        armyCounts(pcs),
+        commitLineup(cells, pcs).root,
+      )
+      .accountsStrict({ inner: { game, signer: p1.publicKey } })
+      .signers([p1])
+      .rpc();
+  };
+
+  const last = lineup.pcs.length - 1;
+  const withLast = (piece: number) => [...lineup.pcs.slice(0, last), piece];
+
+  await expectError(submit(withLast(Piece.Flag)), 'TooManyFlag');
+  await expectError(submit(withLast(Piece.Trap)), 'TooManyTrap');
+
+  // the flag is the first piece of the lineup
+  const noFlag = [Piece.Rock, ...lineup.pcs.slice(1)];
+  await expectError(submit(noFlag), 'TooFewFlag');
+
+  // the fixed lineup itself is fine
+  await submit(lineup.pcs);
+};
@@ -1,47 +1,58 @@
 import { submitFixedLineup } from './submitFixedLineup';
 import { decodeGame, printBoard } from './cells';
 import { Choice } from './types';
+import { revealBattle, weaponCommitment } from './commit';
+import { randomBytes } from 'crypto';
 
 export const playWithTiebreak = async () => {
-  const { program, p0, p1, game } = await submitFixedLineup();
+  const { program, p0, p1, game, lineup0, lineup1 } =
+    await submitFixedLineup();
+  const reveal = () => revealBattle(program, game, p0, p1, lineup0, lineup1!);
 
   await program.methods
     .movePieceXy(3, 4, 3, 3)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(0, 1, 0, 2)
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(3, 3, 3, 2)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(1, 1, 1, 2)
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(3, 2, 4, 2)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(2, 1, 2, 2)
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(4, 2, 4, 1)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
   let gAfterTrap: any = await program.account.game.fetch(game);
   let afterTrapDecoded = decodeGame(gAfterTrap);
@@ -52,19 +63,34 @@ export const playWithTiebreak = async () => {
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(0, 4, 0, 3)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
+  const salt0 = randomBytes(32);
+  const salt1 = randomBytes(32);
   await program.methods
-    .chooseWeapon(Choice.Rock)
+    .commitWeapon(weaponCommitment(Choice.Rock, salt0))
     .accountsStrict({ game, signer: p0 })
     .rpc();
 
   await program.methods
-    .chooseWeapon(Choice.Scissors)
+    .commitWeapon(weaponCommitment(Choice.Scissors, salt1))
+    .accountsStrict({ game, signer: p1.publicKey })
+    .signers([p1])
+    .rpc();
+
+  await program.methods
+    .revealWeapon(Choice.Rock, [...salt0])
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  await program.methods
+    .revealWeapon(Choice.Scissors, [...salt1])
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
@@ -74,39 +100,46 @@ export const playWithTiebreak = async () => {
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(0, 3, 0, 2)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(3, 2, 3, 3)
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(0, 2, 1, 2)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(3, 3, 3, 4)
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(4, 4, 4, 3)
     .accountsStrict({ game, signer: p0 })
     .rpc();
+  await reveal();
 
   await program.methods
     .movePieceXy(3, 4, 3, 5)
     .accountsStrict({ game, signer: p1.publicKey })
     .signers([p1])
     .rpc();
+  await reveal();
 
   // final
   const gFinal: any = await program.account.game.fetch(game);
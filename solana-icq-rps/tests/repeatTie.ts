@@ -0,0 +1,77 @@
+import { expect } from 'chai';
+import { randomBytes } from 'crypto';
+import { CLASSIC_CONFIG } from './setupGame';
+import { startCustomGame } from './customGame';
+import { decodeGame, toIdx } from './cells';
+import { weaponCommitment } from './commit';
+import { sleep } from './errors';
+import { Choice, Owner, Piece } from './types';
+
+// tied weapon rounds are replayed, up to three rounds in all
+const REPEAT_CONFIG = {
+  ...CLASSIC_CONFIG,
+  tieBreak: { repeat: {} },
+  maxTieRounds: 3,
+  army: { ...CLASSIC_CONFIG.army, size: 3 },
+};
+
+// Two rocks meet; the tie-break ties twice and is replayed, then p0's
+// paper beats p1's rock in the third round.
+export const repeatedTieRounds = async () => {
+  const { program, as, move, reveal, fetch } = await startCustomGame(
+    REPEAT_CONFIG,
+    [
+      [3, 5, Piece.Flag],
+      [3, 4, Piece.Rock],
+      [0, 4, Piece.Paper],
+    ],
+    [
+      [3, 0, Piece.Flag],
+      [3, 1, Piece.Rock],
+      [0, 1, Piece.Scissors],
+    ],
+  );
+
+  const rounds: any[] = [];
+  const listener = program.addEventListener('tieRound', (e) =>
+    rounds.push(e),
+  );
+
+  const playRound = async (c0: number, c1: number) => {
+    const [salt0, salt1] = [randomBytes(32), randomBytes(32)];
+    await as('p0', program.methods.commitWeapon(weaponCommitment(c0, salt0)));
+    await as('p1', program.methods.commitWeapon(weaponCommitment(c1, salt1)));
+    await as('p0', program.methods.revealWeapon(c0, [...salt0]));
+    await as('p1', program.methods.revealWeapon(c1, [...salt1]));
+  };
+
+  await move('p0', 3, 4, 3, 3);
+  await move('p1', 3, 1, 3, 2);
+  await move('p0', 3, 3, 3, 2);
+  await reveal();
+  expect((await fetch()).tiePending).to.equal(true);
+
+  await playRound(Choice.Rock, Choice.Rock);
+  let raw = await fetch();
+  expect(raw.tiePending).to.equal(true);
+  expect(raw.tieRound).to.equal(1);
+
+  await playRound(Choice.Paper, Choice.Paper);
+  expect((await fetch()).tieRound).to.equal(2);
+
+  await playRound(Choice.Paper, Choice.Rock);
+  raw = await fetch();
+  expect(raw.tiePending).to.equal(false);
+  const g = decodeGame(raw);
+  expect(g.owners[toIdx(3, 2)]).to.equal(Owner.P0);
+  expect(g.live1).to.equal(2);
+
+  // events arrive over the websocket after the transaction lands
+  await sleep(1000);
+  await program.removeEventListener(listener);
+  expect(rounds.map((r) => [r.round, r.outcome])).to.deep.equal([
+    [0, 0],
+    [1, 0],
+    [2, 1],
+  ]);
+};
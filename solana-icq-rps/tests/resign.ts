@@ -0,0 +1,24 @@
+import { expect } from 'chai';
+import { submitFixedLineup } from './submitFixedLineup';
+import { decodeGame } from './cells';
+import { Phase } from './types';
+
+export const resign = async () => {
+  const { program, p0, p1, game } = await submitFixedLineup();
+
+  await program.methods
+    .resign()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  const decoded = decodeGame(await program.account.game.fetch(game));
+  expect(decoded.phase).to.equal(Phase.Finished);
+  expect(decoded.winner!.toString()).to.equal(p1.publicKey.toString());
+
+  await program.methods
+    .closeGame()
+    .accountsStrict({ game, payer: p0, signer: p1.publicKey })
+    .signers([p1])
+    .rpc();
+  expect(await program.account.game.fetchNullable(game)).to.be.null;
+};
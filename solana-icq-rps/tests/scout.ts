@@ -0,0 +1,52 @@
+import { expect } from 'chai';
+import { CLASSIC_CONFIG } from './setupGame';
+import { startCustomGame } from './customGame';
+import { decodeGame, toIdx } from './cells';
+import { expectError } from './errors';
+import { Owner, Phase, Piece } from './types';
+
+const SCOUT_CONFIG = {
+  ...CLASSIC_CONFIG,
+  army: {
+    ...CLASSIC_CONFIG.army,
+    size: 3,
+    max: [3, 3, 3, 1, 0, 0, 0, 3, 0],
+  },
+};
+
+// A scout runs down an open column; nothing may jump over a piece; and a
+// hidden rock that moved like a scout forfeits the game when revealed.
+export const scoutLongMoves = async () => {
+  const { p1, move, revealAt, fetch } = await startCustomGame(
+    SCOUT_CONFIG,
+    [
+      [3, 5, Piece.Flag],
+      [0, 4, Piece.Scout],
+      [6, 4, Piece.Rock],
+    ],
+    [
+      [3, 0, Piece.Flag],
+      [0, 0, Piece.Rock],
+      [6, 1, Piece.Paper],
+    ],
+  );
+
+  await move('p0', 0, 4, 0, 2);
+  let g = decodeGame(await fetch());
+  expect(g.owners[toIdx(0, 2)]).to.equal(Owner.P0);
+  expect(g.owners[toIdx(0, 4)]).to.equal(Owner.None);
+
+  // the scout on (0, 2) stands in the way
+  await expectError(move('p1', 0, 0, 0, 3), 'InvalidMove');
+  await move('p1', 6, 1, 6, 2);
+
+  // hidden pieces may bluff a long attack...
+  await move('p0', 6, 4, 6, 2);
+  // ...but this one is a rock, still on its cell until the battle ends
+  await revealAt('p0', 6, 4);
+
+  const raw = await fetch();
+  g = decodeGame(raw);
+  expect(g.phase).to.equal(Phase.Finished);
+  expect(raw.winner.toBase58()).to.equal(p1.publicKey.toBase58());
+};
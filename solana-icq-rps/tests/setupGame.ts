@@ -1,19 +1,116 @@
 import * as anchor from '@coral-xyz/anchor';
 import { Program } from '@coral-xyz/anchor';
-import { Keypair } from '@solana/web3.js';
+import { Keypair, PublicKey } from '@solana/web3.js';
 import { SolanaIcqRps } from '../target/types/solana_icq_rps';
 import { airdropIfNeeded } from './pdas';
-import { buildFullLineupWithFlag, toIdx, u8 } from './cells';
+import { armyCounts, buildFullLineupWithFlag, toIdx, u8 } from './cells';
+import { commitLineup, LineupSecret } from './commit';
+import { fundTokenWager, TokenWager } from './token';
 const { randomBytes } = require('crypto');
 
+export const LINEUP_TIMEOUT_SECS = 600;
+export const TURN_TIMEOUT_SECS = 300;
+export const FEE_BPS = 250;
+
+// the original 7x6 board
+export const CLASSIC_CONFIG = {
+  width: 7,
+  height: 6,
+  spawnDepth: 2,
+  trapRows: 2,
+  flagCanMove: true,
+  singleUseTraps: false,
+  weapons: { classic: {} },
+  scoutWeapon: { rock: {} },
+  terrain: { open: {} },
+  blocked: new Array(13).fill(0),
+  army: {
+    mode: { composition: {} },
+    size: 14,
+    min: [0, 0, 0, 1, 0, 0, 0, 0, 0],
+    max: [14, 14, 14, 1, 1, 0, 0, 0, 0],
+    cost: [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    budget: 0,
+    draft: false,
+    pool: [0, 0, 0, 0, 0, 0, 0, 0, 0],
+  },
+  tieBreak: { weapons: {} },
+  maxTieRounds: 1,
+  limitedWeapons: false,
+  weaponStock: [0, 0, 0, 0, 0],
+  stalemate: { loss: {} },
+  lineupTimeoutSecs: LINEUP_TIMEOUT_SECS,
+  turnTimeoutSecs: TURN_TIMEOUT_SECS,
+};
+
 export interface GameSetupReturn {
   program: anchor.Program<any>;
   p0: anchor.web3.PublicKey;
   p1: anchor.web3.Keypair;
   game: anchor.web3.PublicKey;
+  escrow: anchor.web3.PublicKey;
+  lineup0: LineupSecret;
+  lineup1?: LineupSecret;
+  token?: TokenWager;
 }
 
-export const setupGame = async (): Promise<GameSetupReturn> => {
+// optional SPL wager accounts, left out for SOL games
+export const NO_TOKEN = { mint: null, vault: null, tokenProgram: null };
+
+export const escrowPda = (
+  program: anchor.Program<any>,
+  game: anchor.web3.PublicKey,
+) =>
+  anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('escrow'), game.toBuffer()],
+    program.programId,
+  )[0];
+
+export const BPF_LOADER_UPGRADEABLE = new PublicKey(
+  'BPFLoaderUpgradeab1e11111111111111111111111',
+);
+
+export const configPda = (program: anchor.Program<any>) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from('config')],
+    program.programId,
+  )[0];
+
+// where the upgradeable loader keeps the program's upgrade authority
+export const programDataPda = (program: anchor.Program<any>) =>
+  PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE,
+  )[0];
+
+// The global fee config has to exist before any game can be created; the
+// provider wallet deployed the program, so it acts as admin and treasury on
+// the local validator.
+export const ensureConfig = async (program: anchor.Program<any>) => {
+  const admin = (program.provider as anchor.AnchorProvider).wallet.publicKey;
+  const config = configPda(program);
+  if (!(await program.account.config.fetchNullable(config))) {
+    await program.methods
+      .initConfig(FEE_BPS, admin)
+      .accountsStrict({
+        config,
+        admin,
+        program: program.programId,
+        programData: programDataPda(program),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return config;
+};
+
+// Passing a mint plays for SPL tokens instead of SOL; both players are
+// funded with one stake of it.
+export const setupGame = async (
+  wager = 0,
+  config: any = CLASSIC_CONFIG,
+  mint?: PublicKey,
+): Promise<GameSetupReturn> => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const program = anchor.workspace.solanaIcqRps as Program<SolanaIcqRps>;
@@ -30,14 +127,23 @@ export const setupGame = async (): Promise<GameSetupReturn> => {
     [Buffer.from('game'), p0.toBuffer(), Buffer.from(nonce)],
     program.programId,
   );
+  const escrow = escrowPda(program, game);
+  const configPda = await ensureConfig(program);
+  const token =
+    mint &&
+    (await fundTokenWager(program, game, mint, [p0, p1.publicKey], wager));
 
   // create
   await program.methods
-    .createGame([...nonce])
+    .createGame([...nonce], new anchor.BN(wager), config)
     .accountsStrict({
       game,
+      config: configPda,
+      escrow,
       payer: p0,
       systemProgram: anchor.web3.SystemProgram.programId,
+      ...(token?.accounts ?? NO_TOKEN),
+      payerToken: token?.player0Token ?? null,
     })
     .rpc();
 
@@ -48,19 +154,28 @@ export const setupGame = async (): Promise<GameSetupReturn> => {
     xs: xs0,
     ys: ys0,
     pcs: pcs0,
+    cells: cells0,
   } = buildFullLineupWithFlag(/* isP0 */ true, p0FlagIdx, p0TrapIdx);
+  const lineup0 = commitLineup(cells0, pcs0);
 
   await program.methods
-    .submitLineupXy(u8(xs0), u8(ys0), u8(pcs0))
+    .submitLineupXy(u8(xs0), u8(ys0), armyCounts(pcs0), lineup0.root)
     .accountsStrict({ inner: { game, signer: p0 } })
     .rpc();
 
   // join
   await program.methods
     .joinGame()
-    .accountsStrict({ game, joiner: p1.publicKey })
+    .accountsStrict({
+      game,
+      escrow,
+      joiner: p1.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...(token?.accounts ?? NO_TOKEN),
+      joinerToken: token?.player1Token ?? null,
+    })
     .signers([p1])
     .rpc();
 
-  return { program, p0, p1, game };
+  return { program, p0, p1, game, escrow, lineup0, token };
 };
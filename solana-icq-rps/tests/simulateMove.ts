@@ -0,0 +1,34 @@
+import { expect } from 'chai';
+import { submitFixedLineup } from './submitFixedLineup';
+import { decodeGame, toIdx } from './cells';
+import { Owner } from './types';
+
+export const simulateMoves = async () => {
+  const { program, p0, p1, game } = await submitFixedLineup();
+  const before = decodeGame(await program.account.game.fetch(game));
+
+  const preview = await program.methods
+    .simulateMove(toIdx(3, 4), toIdx(3, 3))
+    .accountsStrict({ game, signer: p0 })
+    .view();
+  expect(preview.result).to.have.property('moved');
+  expect(preview.gameOver).to.be.null;
+
+  const outOfTurn = await program.methods
+    .simulateMove(toIdx(0, 1), toIdx(0, 2))
+    .accountsStrict({ game, signer: p1.publicKey })
+    .signers([p1])
+    .view();
+  expect(outOfTurn.result.illegal.error).to.be.at.least(6000);
+
+  // a preview leaves the game untouched
+  const after = decodeGame(await program.account.game.fetch(game));
+  expect(after.owners).to.deep.equal(before.owners);
+
+  await program.methods
+    .movePiece(toIdx(3, 4), toIdx(3, 3))
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+  const moved = decodeGame(await program.account.game.fetch(game));
+  expect(moved.owners[toIdx(3, 3)]).to.equal(Owner.P0);
+};
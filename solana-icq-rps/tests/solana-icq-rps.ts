@@ -1,7 +1,123 @@
 import { playWithTiebreak } from './playWithTiebreak';
+import { resign } from './resign';
+import { cancelGame } from './cancelGame';
+import { WAGER, wagerPaidToWinner } from './wager';
+import {
+  drawOfferKeepsTheTurnClock,
+  drawOfferLapsesOnMove,
+  mutualDraw,
+} from './draw';
+import { draftArmies } from './draft';
+import { budgetArmies } from './budget';
+import { lizardPoisonsSpock } from './extendedWeapons';
+import { customTerrainStaysOutOfSpawns, lakesBlockMoves } from './terrain';
+import { scoutLongMoves } from './scout';
+import { bombTakesTheLastPieces } from './bomb';
+import { repeatedTieRounds } from './repeatTie';
+import { simulateMoves } from './simulateMove';
+import { lineupTimeout, turnTimeout } from './timeout';
+import {
+  tokenWagerPaidToWinner,
+  tokenWagerRefundedOnCancel,
+  tokenWagerRefundedOnDraw,
+} from './splWager';
+import { adminUpdatesConfig } from './admin';
+import { lineupNamesTheBadPieceKind } from './lineup';
 
 describe('solana-icq-rps', () => {
   it('game full flow', async () => {
     await playWithTiebreak();
   });
+
+  it('resign gives the win to the opponent, then the game is closed', async () => {
+    await resign();
+  });
+
+  it('creator cancels an unjoined game', async () => {
+    await cancelGame();
+  });
+
+  it('wager pot goes to the winner', async () => {
+    await wagerPaidToWinner();
+  });
+
+  it('cancel refunds the creator wager', async () => {
+    await cancelGame(WAGER);
+  });
+
+  it('token wager pot goes to the winner', async () => {
+    await tokenWagerPaidToWinner();
+  });
+
+  it('a drawn token game refunds both stakes', async () => {
+    await tokenWagerRefundedOnDraw();
+  });
+
+  it('cancel refunds the creator token wager and closes the vault', async () => {
+    await tokenWagerRefundedOnCancel();
+  });
+
+  it('accepted draw offer finishes without a winner', async () => {
+    await mutualDraw();
+  });
+
+  it('a draw offer lapses once the next move is played', async () => {
+    await drawOfferLapsesOnMove();
+  });
+
+  it('offering and withdrawing a draw does not stop the turn clock', async () => {
+    await drawOfferKeepsTheTurnClock();
+  });
+
+  it('budget armies are held to their point budget', async () => {
+    await budgetArmies();
+  });
+
+  it('lizard beats spock in the extended weapon set', async () => {
+    await lizardPoisonsSpock();
+  });
+
+  it('lakes are impassable', async () => {
+    await lakesBlockMoves();
+  });
+
+  it('custom terrain blocks cells outside the spawn zones', async () => {
+    await customTerrainStaysOutOfSpawns();
+  });
+
+  it('scouts move far, but a long-moving non-scout forfeits', async () => {
+    await scoutLongMoves();
+  });
+
+  it('a bomb taking the last pieces of both sides ends in a draw', async () => {
+    await bombTakesTheLastPieces();
+  });
+
+  it('tied weapon rounds are replayed until one is decisive', async () => {
+    await repeatedTieRounds();
+  });
+
+  it('players draft their armies in turns from the pool', async () => {
+    await draftArmies();
+  });
+
+  it('simulated moves predict the outcome without changing the game', async () => {
+    await simulateMoves();
+  });
+
+  it('a stalled turn can be claimed once the turn timeout passes', async () => {
+    await turnTimeout();
+  });
+
+  it('a missing lineup ends the game without a winner after the timeout', async () => {
+    await lineupTimeout();
+  });
+
+  it('only the admin can update the fee config, up to the cap', async () => {
+    await adminUpdatesConfig();
+  });
+
+  it('a lineup breaking the army rules names the piece kind', async () => {
+    await lineupNamesTheBadPieceKind();
+  });
 });
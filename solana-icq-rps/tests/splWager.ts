@@ -0,0 +1,93 @@
+import * as anchor from '@coral-xyz/anchor';
+import { expect } from 'chai';
+import { cancelGame } from './cancelGame';
+import { submitFixedLineup } from './submitFixedLineup';
+import { CLASSIC_CONFIG, FEE_BPS } from './setupGame';
+import { createMint, tokenBalance } from './token';
+
+export const TOKEN_WAGER = 1_000_000;
+
+const testMint = async () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIcqRps as anchor.Program<any>;
+  return { program, mint: await createMint(program) };
+};
+
+const claimTokenPot = async ({ program, p0, p1, game, escrow, token }: any) =>
+  program.methods
+    .claimPot()
+    .accountsStrict({
+      game,
+      escrow,
+      player0: p0,
+      player1: p1.publicKey,
+      treasury: p0,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...token.accounts,
+      player0Token: token.player0Token,
+      player1Token: token.player1Token,
+      // the provider wallet is both p0 and the treasury
+      treasuryToken: token.player0Token,
+    })
+    .rpc();
+
+// Both stakes go into the game's vault on create and join; p0 resigns and
+// p1 is paid the token pot minus the protocol fee.
+export const tokenWagerPaidToWinner = async () => {
+  const { program, mint } = await testMint();
+  const setup = await submitFixedLineup(TOKEN_WAGER, CLASSIC_CONFIG, mint);
+  const { p0, game, token } = setup;
+  const conn = program.provider.connection;
+
+  expect(await tokenBalance(program, token.accounts.vault)).to.equal(
+    2 * TOKEN_WAGER,
+  );
+  expect(await tokenBalance(program, token.player0Token)).to.equal(0);
+  expect(await tokenBalance(program, token.player1Token)).to.equal(0);
+
+  await program.methods
+    .resign()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+  await claimTokenPot(setup);
+
+  const fee = Math.floor((2 * TOKEN_WAGER * FEE_BPS) / 10_000);
+  expect(await tokenBalance(program, token.player1Token)).to.equal(
+    2 * TOKEN_WAGER - fee,
+  );
+  expect(await tokenBalance(program, token.player0Token)).to.equal(fee);
+  // the emptied vault is closed back to the creator
+  expect(await conn.getAccountInfo(token.accounts.vault)).to.be.null;
+};
+
+// A drawn token game gives each player their own stake back, fee free.
+export const tokenWagerRefundedOnDraw = async () => {
+  const { program, mint } = await testMint();
+  const setup = await submitFixedLineup(TOKEN_WAGER, CLASSIC_CONFIG, mint);
+  const { p0, p1, game, token } = setup;
+
+  await program.methods
+    .offerDraw()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+  await program.methods
+    .acceptDraw()
+    .accountsStrict({ game, signer: p1.publicKey })
+    .signers([p1])
+    .rpc();
+  await claimTokenPot(setup);
+
+  expect(await tokenBalance(program, token.player0Token)).to.equal(
+    TOKEN_WAGER,
+  );
+  expect(await tokenBalance(program, token.player1Token)).to.equal(
+    TOKEN_WAGER,
+  );
+};
+
+// The creator backs out before anyone joins: the stake leaves the vault and
+// the vault is closed.
+export const tokenWagerRefundedOnCancel = async () => {
+  const { mint } = await testMint();
+  await cancelGame(TOKEN_WAGER, mint);
+};
@@ -1,5 +1,8 @@
-import { setupGame, GameSetupReturn } from './setupGame';
+import { PublicKey } from '@solana/web3.js';
+import { setupGame, GameSetupReturn, CLASSIC_CONFIG } from './setupGame';
+import { commitLineup } from './commit';
 import {
+  armyCounts,
   u8,
   decodeGame,
   printBoard,
@@ -7,8 +10,16 @@ import {
   buildFullLineupWithFlag,
 } from './cells';
 
-export const submitFixedLineup = async (): Promise<GameSetupReturn> => {
-  const { program, p0, p1, game } = await setupGame();
+export const submitFixedLineup = async (
+  wager = 0,
+  config: any = CLASSIC_CONFIG,
+  mint?: PublicKey,
+): Promise<GameSetupReturn> => {
+  const { program, p0, p1, game, escrow, lineup0, token } = await setupGame(
+    wager,
+    config,
+    mint,
+  );
   console.log('setup done');
 
   // lineup p1
@@ -18,10 +29,12 @@ export const submitFixedLineup = async (): Promise<GameSetupReturn> => {
     xs: xs1,
     ys: ys1,
     pcs: pcs1,
+    cells: cells1,
   } = buildFullLineupWithFlag(/* isP0 */ false, p1FlagIdx, p1TrapIdx);
+  const lineup1 = commitLineup(cells1, pcs1);
 
   await program.methods
-    .submitLineupXy(u8(xs1), u8(ys1), u8(pcs1))
+    .submitLineupXy(u8(xs1), u8(ys1), armyCounts(pcs1), lineup1.root)
     .accountsStrict({ inner: { game, signer: p1.publicKey } })
     .signers([p1])
     .rpc();
@@ -30,5 +43,5 @@ export const submitFixedLineup = async (): Promise<GameSetupReturn> => {
   let gDec = decodeGame(g);
   printBoard(gDec.owners, gDec.pieces);
 
-  return { program, p0, p1, game };
+  return { program, p0, p1, game, escrow, lineup0, lineup1, token };
 };
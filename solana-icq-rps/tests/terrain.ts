@@ -0,0 +1,64 @@
+import { expect } from 'chai';
+import { CLASSIC_CONFIG } from './setupGame';
+import { createCustomGame, Placement, startCustomGame } from './customGame';
+import { decodeGame, toIdx } from './cells';
+import { expectError } from './errors';
+import { Owner, Piece } from './types';
+
+const LINEUP0: Placement[] = [
+  [3, 5, Piece.Flag],
+  [1, 4, Piece.Rock],
+  [0, 4, Piece.Paper],
+];
+const LINEUP1: Placement[] = [
+  [3, 0, Piece.Flag],
+  [1, 1, Piece.Rock],
+  [0, 1, Piece.Scissors],
+];
+
+const SMALL_ARMY = { ...CLASSIC_CONFIG.army, size: 3 };
+
+// one bit per cell index, as in `GameConfig::blocked`
+const blockedBits = (cells: number[]) => {
+  const bits = new Array(13).fill(0);
+  cells.forEach((c) => (bits[c >> 3] |= 1 << (c & 7)));
+  return bits;
+};
+
+// On the 7x6 board the lakes fill columns 1 and 5 of the two middle rows.
+export const lakesBlockMoves = async () => {
+  const { move, fetch } = await startCustomGame(
+    { ...CLASSIC_CONFIG, terrain: { lakes: {} }, army: SMALL_ARMY },
+    LINEUP0,
+    LINEUP1,
+  );
+
+  const g = decodeGame(await fetch());
+  const lakes = [toIdx(1, 2), toIdx(1, 3), toIdx(5, 2), toIdx(5, 3)];
+  g.owners.forEach((o, i) =>
+    expect(o === Owner.Blocked, `cell ${i}`).to.equal(lakes.includes(i)),
+  );
+
+  await expectError(move('p0', 1, 4, 1, 3), 'CellBlocked');
+  await move('p0', 0, 4, 0, 3);
+  await expectError(move('p1', 1, 1, 1, 2), 'CellBlocked');
+};
+
+// Custom terrain may block the middle rows but never a spawn zone.
+export const customTerrainStaysOutOfSpawns = async () => {
+  const custom = (cells: number[]) => ({
+    ...CLASSIC_CONFIG,
+    terrain: { custom: {} },
+    blocked: blockedBits(cells),
+    army: SMALL_ARMY,
+  });
+
+  await expectError(createCustomGame(custom([toIdx(0, 5)])), 'InvalidTerrain');
+
+  const { move } = await startCustomGame(
+    custom([toIdx(0, 3)]),
+    LINEUP0,
+    LINEUP1,
+  );
+  await expectError(move('p0', 0, 4, 0, 3), 'CellBlocked');
+};
@@ -0,0 +1,76 @@
+import { expect } from 'chai';
+import { CLASSIC_CONFIG, setupGame } from './setupGame';
+import { submitFixedLineup } from './submitFixedLineup';
+import {
+  armyCounts,
+  buildFullLineupWithFlag,
+  decodeGame,
+  toIdx,
+  u8,
+} from './cells';
+import { commitLineup } from './commit';
+import { expectError, sleep } from './errors';
+import { Phase } from './types';
+
+// one second clocks, waited out with some slack for the validator clock
+export const FAST_CONFIG = {
+  ...CLASSIC_CONFIG,
+  lineupTimeoutSecs: 1,
+  turnTimeoutSecs: 1,
+};
+export const WAIT_MS = 3000;
+
+// player0 is to move and lets the turn clock run out
+export const turnTimeout = async () => {
+  const { program, p0, p1, game } = await submitFixedLineup(0, FAST_CONFIG);
+  const claim = (signer: 'p0' | 'p1') => {
+    const m = program.methods.claimTimeout();
+    return signer === 'p0'
+      ? m.accountsStrict({ game, signer: p0 }).rpc()
+      : m
+          .accountsStrict({ game, signer: p1.publicKey })
+          .signers([p1])
+          .rpc();
+  };
+
+  // the player who is stalling cannot claim
+  await expectError(claim('p0'), 'NothingToClaim');
+
+  await sleep(WAIT_MS);
+  await claim('p1');
+
+  const decoded = decodeGame(await program.account.game.fetch(game));
+  expect(decoded.phase).to.equal(Phase.Finished);
+  expect(decoded.winner!.toString()).to.equal(p1.publicKey.toString());
+};
+
+// player1 joins but never places a lineup: too late to place one, and
+// player0, who was kept waiting, wins
+export const lineupTimeout = async () => {
+  const { program, p0, p1, game } = await setupGame(0, FAST_CONFIG);
+
+  await sleep(WAIT_MS);
+  const { xs, ys, pcs, cells } = buildFullLineupWithFlag(
+    /* isP0 */ false,
+    toIdx(3, 0),
+    toIdx(4, 1),
+  );
+  const lineup1 = commitLineup(cells, pcs);
+  await expectError(
+    program.methods
+      .submitLineupXy(u8(xs), u8(ys), armyCounts(pcs), lineup1.root)
+      .accountsStrict({ inner: { game, signer: p1.publicKey } })
+      .signers([p1])
+      .rpc(),
+    'PlaceFlagDeadlinePassed',
+  );
+
+  await program.methods
+    .claimTimeout()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  const decoded = decodeGame(await program.account.game.fetch(game));
+  expect(decoded.phase).to.equal(Phase.Finished);
+  expect(decoded.winner!.toString()).to.equal(p0.toString());
+};
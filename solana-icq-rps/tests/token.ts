@@ -0,0 +1,165 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from '@solana/web3.js';
+
+// Minimal SPL token helpers built from raw token program instructions, so
+// the tests don't need @solana/spl-token.
+export const TOKEN_PROGRAM_ID = anchor.utils.token.TOKEN_PROGRAM_ID;
+
+const MINT_SIZE = 82;
+const ACCOUNT_SIZE = 165;
+
+// token program instruction tags
+const INITIALIZE_MINT2 = 20;
+const INITIALIZE_ACCOUNT3 = 18;
+const MINT_TO = 7;
+
+export interface TokenWager {
+  // the optional accounts every wager instruction takes
+  accounts: { mint: PublicKey; vault: PublicKey; tokenProgram: PublicKey };
+  player0Token: PublicKey;
+  player1Token: PublicKey;
+}
+
+export const vaultPda = (
+  program: anchor.Program<any>,
+  game: anchor.web3.PublicKey,
+) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from('vault'), game.toBuffer()],
+    program.programId,
+  )[0];
+
+const providerOf = (program: anchor.Program<any>) =>
+  program.provider as anchor.AnchorProvider;
+
+const createTokenProgramAccount = async (
+  program: anchor.Program<any>,
+  space: number,
+  init: (account: PublicKey) => TransactionInstruction,
+) => {
+  const provider = providerOf(program);
+  const account = Keypair.generate();
+  const lamports =
+    await provider.connection.getMinimumBalanceForRentExemption(space);
+  await provider.sendAndConfirm(
+    new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.wallet.publicKey,
+        newAccountPubkey: account.publicKey,
+        lamports,
+        space,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      init(account.publicKey),
+    ),
+    [account],
+  );
+  return account.publicKey;
+};
+
+// A fresh mint with the provider wallet as mint authority.
+export const createMint = async (program: anchor.Program<any>, decimals = 6) =>
+  createTokenProgramAccount(
+    program,
+    MINT_SIZE,
+    (mint) =>
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+        data: Buffer.from([
+          INITIALIZE_MINT2,
+          decimals,
+          ...providerOf(program).wallet.publicKey.toBuffer(),
+          0, // no freeze authority
+        ]),
+      }),
+  );
+
+export const createTokenAccount = async (
+  program: anchor.Program<any>,
+  mint: PublicKey,
+  owner: PublicKey,
+) =>
+  createTokenProgramAccount(
+    program,
+    ACCOUNT_SIZE,
+    (account) =>
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [
+          { pubkey: account, isSigner: false, isWritable: true },
+          { pubkey: mint, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.from([INITIALIZE_ACCOUNT3, ...owner.toBuffer()]),
+      }),
+  );
+
+export const mintTo = async (
+  program: anchor.Program<any>,
+  mint: PublicKey,
+  to: PublicKey,
+  amount: number,
+) => {
+  const provider = providerOf(program);
+  await provider.sendAndConfirm(
+    new Transaction().add(
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [
+          { pubkey: mint, isSigner: false, isWritable: true },
+          { pubkey: to, isSigner: false, isWritable: true },
+          {
+            pubkey: provider.wallet.publicKey,
+            isSigner: true,
+            isWritable: false,
+          },
+        ],
+        data: Buffer.from([
+          MINT_TO,
+          ...new anchor.BN(amount).toArrayLike(Buffer, 'le', 8),
+        ]),
+      }),
+    ),
+  );
+};
+
+export const tokenBalance = async (
+  program: anchor.Program<any>,
+  account: PublicKey,
+) =>
+  Number(
+    (await program.provider.connection.getTokenAccountBalance(account)).value
+      .amount,
+  );
+
+// Gives both players a token account of `mint` holding exactly one stake.
+export const fundTokenWager = async (
+  program: anchor.Program<any>,
+  game: PublicKey,
+  mint: PublicKey,
+  players: [PublicKey, PublicKey],
+  wager: number,
+): Promise<TokenWager> => {
+  const [player0Token, player1Token] = await Promise.all(
+    players.map(async (owner) => {
+      const account = await createTokenAccount(program, mint, owner);
+      await mintTo(program, mint, account, wager);
+      return account;
+    }),
+  );
+  return {
+    accounts: {
+      mint,
+      vault: vaultPda(program, game),
+      tokenProgram: TOKEN_PROGRAM_ID,
+    },
+    player0Token,
+    player1Token,
+  };
+};
@@ -9,6 +9,11 @@ export const Piece = {
   Scissors: 3,
   Flag: 4,
   Trap: 5,
+  Unknown: 6,
+  Lizard: 7,
+  Spock: 8,
+  Scout: 9,
+  Bomb: 10,
 } as const;
 export type Piece = (typeof Piece)[keyof typeof Piece];
 
@@ -16,6 +21,7 @@ export const Owner = {
   None: 0,
   P0: 1,
   P1: 2,
+  Blocked: 3,
 } as const;
 export type Owner = (typeof Owner)[keyof typeof Owner];
 
@@ -26,6 +32,8 @@ export const Phase = {
   LineupP1Set: 3,
   Active: 4,
   Finished: 5,
+  DraftP0Turn: 6,
+  DraftP1Turn: 7,
 } as const;
 export type Phase = (typeof Phase)[keyof typeof Phase];
 
@@ -34,5 +42,7 @@ export const Choice = {
   Rock: 1,
   Paper: 2,
   Scissors: 3,
+  Lizard: 4,
+  Spock: 5,
 } as const;
 export type Choice = (typeof Choice)[keyof typeof Choice];
@@ -0,0 +1,49 @@
+import * as anchor from '@coral-xyz/anchor';
+import { LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { submitFixedLineup } from './submitFixedLineup';
+import { FEE_BPS, NO_TOKEN } from './setupGame';
+
+export const WAGER = LAMPORTS_PER_SOL / 10;
+
+// p0 resigns a wagered game and p1 claims both stakes, minus the protocol
+// fee that goes to the treasury (the provider wallet, which is p0 here).
+export const wagerPaidToWinner = async () => {
+  const { program, p0, p1, game, escrow } = await submitFixedLineup(WAGER);
+  const conn = program.provider.connection;
+
+  expect(await conn.getBalance(escrow)).to.equal(2 * WAGER);
+
+  await program.methods
+    .resign()
+    .accountsStrict({ game, signer: p0 })
+    .rpc();
+
+  const before = await conn.getBalance(p1.publicKey);
+  await program.methods
+    .claimPot()
+    .accountsStrict({
+      game,
+      escrow,
+      player0: p0,
+      player1: p1.publicKey,
+      treasury: p0,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      ...NO_TOKEN,
+      player0Token: null,
+      player1Token: null,
+      treasuryToken: null,
+    })
+    .rpc();
+
+  const fee = Math.floor((2 * WAGER * FEE_BPS) / 10_000);
+  expect(await conn.getBalance(p1.publicKey)).to.equal(
+    before + 2 * WAGER - fee,
+  );
+  expect(await conn.getBalance(escrow)).to.equal(0);
+
+  await program.methods
+    .closeGame()
+    .accountsStrict({ game, payer: p0, signer: p0 })
+    .rpc();
+};